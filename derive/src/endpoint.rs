@@ -9,7 +9,7 @@ use quote::{format_ident, quote, quote_spanned, ToTokens};
 use std::str::FromStr;
 use syn::{
 	parse::Parse, spanned::Spanned, Attribute, Error, Expr, FnArg, ItemFn, LitBool, LitStr, Meta,
-	PatType, Result, ReturnType, Type
+	Pat, PatType, Path, Result, ReturnType, Type
 };
 use unindent::Unindent;
 
@@ -283,6 +283,7 @@ impl HandlerArgType {
 }
 
 struct HandlerArg {
+	ident: Ident,
 	ident_span: Span,
 	ty: HandlerArgType
 }
@@ -346,12 +347,17 @@ fn interpret_arg_ty(attrs: &[Attribute], name: &str, ty: Type) -> Result<Handler
 	Ok(HandlerArgType::MethodArg(ty))
 }
 
-fn interpret_arg(_index: usize, arg: &PatType) -> Result<HandlerArg> {
+fn interpret_arg(index: usize, arg: &PatType) -> Result<HandlerArg> {
 	let pat = &arg.pat;
 	let orig_name = quote!(#pat);
 	let ty = interpret_arg_ty(&arg.attrs, &orig_name.to_string(), *arg.ty.clone())?;
+	let ident = match pat.as_ref() {
+		Pat::Ident(pat) => pat.ident.clone(),
+		_ => format_ident!("arg{index}")
+	};
 
 	Ok(HandlerArg {
+		ident,
 		ident_span: arg.pat.span(),
 		ty
 	})
@@ -417,6 +423,10 @@ pub fn endpoint_ident(fn_ident: &Ident) -> Ident {
 	format_ident!("{}___gotham_restful_endpoint", fn_ident)
 }
 
+pub fn endpoint_client_ident(fn_ident: &Ident) -> Ident {
+	format_ident!("{}___gotham_restful_client", fn_ident)
+}
+
 macro_rules! error_if_not_openapi {
 	($($ident:ident),*) => {
 		$(
@@ -451,9 +461,13 @@ fn expand_endpoint_type(
 	let mut operation_id: Option<LitStr> = None;
 	let mut schema: Option<Ident> = None;
 	let mut status_codes: Option<Ident> = None;
+	let mut headers: Option<Ident> = None;
 	let mut wants_auth: Option<LitBool> = None;
+	let mut tags: Vec<LitStr> = Vec::new();
+	let mut hidden: Option<Path> = None;
 	for meta in attrs {
 		match meta {
+			Meta::Path(path) if path.is_ident("hidden") => hidden = Some(path),
 			Meta::NameValue(kv) => {
 				if kv.path.is_ident("debug") {
 					debug = kv.value.expect_bool()?.value;
@@ -463,6 +477,10 @@ fn expand_endpoint_type(
 					schema = Some(kv.value.expect_str()?.into_ident())
 				} else if kv.path.is_ident("status_codes") {
 					status_codes = Some(kv.value.expect_str()?.into_ident())
+				} else if kv.path.is_ident("headers") {
+					headers = Some(kv.value.expect_str()?.into_ident())
+				} else if kv.path.is_ident("tag") {
+					tags.push(kv.value.expect_str()?);
 				} else if kv.path.is_ident("wants_auth") {
 					wants_auth = Some(kv.value.expect_bool()?);
 				} else if kv.path.is_ident("method") {
@@ -483,7 +501,14 @@ fn expand_endpoint_type(
 			_ => return Err(Error::new(meta.span(), "Invalid attribute syntax"))
 		}
 	}
-	error_if_not_openapi!(operation_id, schema, status_codes);
+	error_if_not_openapi!(operation_id, schema, status_codes, headers, hidden);
+	#[cfg(not(feature = "openapi"))]
+	if let Some(tag) = tags.first() {
+		return Err(Error::new(
+			tag.span(),
+			"`tag` is only supported with the openapi feature"
+		));
+	}
 	if schema.is_some() != status_codes.is_some() {
 		return Err(Error::new(
 			schema
@@ -518,6 +543,59 @@ fn expand_endpoint_type(
 		});
 	}
 
+	#[allow(unused_mut)]
+	let mut tags_fn: Option<TokenStream> = None;
+	#[cfg(feature = "openapi")]
+	if !tags.is_empty() {
+		tags_fn = Some(quote! {
+			fn tags() -> ::std::vec::Vec<::std::borrow::Cow<'static, ::core::primitive::str>> {
+				::std::vec![#(::std::borrow::Cow::Borrowed(#tags)),*]
+			}
+		});
+	}
+
+	#[allow(unused_mut)]
+	let mut deprecated_fn: Option<TokenStream> = None;
+	#[cfg(feature = "openapi")]
+	if fun
+		.attrs
+		.iter()
+		.any(|attr| attr.path().is_ident("deprecated"))
+	{
+		deprecated_fn = Some(quote! {
+			fn deprecated() -> ::core::primitive::bool {
+				true
+			}
+		});
+	}
+
+	#[allow(unused_mut)]
+	let mut headers_fn: Option<TokenStream> = None;
+	#[cfg(feature = "openapi")]
+	if let Some(headers) = headers {
+		let headers_call = quote_spanned! { headers.span() =>
+			::gotham_restful::private::CustomHeaders::headers(#headers, code)
+		};
+		headers_fn = Some(quote! {
+			fn headers(
+				code: ::gotham_restful::gotham::hyper::StatusCode
+			) -> ::std::vec::Vec<::gotham_restful::ResponseHeader> {
+				#headers_call
+			}
+		});
+	}
+
+	#[allow(unused_mut)]
+	let mut hidden_fn: Option<TokenStream> = None;
+	#[cfg(feature = "openapi")]
+	if hidden.is_some() {
+		hidden_fn = Some(quote! {
+			fn hidden() -> ::core::primitive::bool {
+				true
+			}
+		});
+	}
+
 	// extract arguments into pattern, ident and type
 	let args = fun
 		.sig
@@ -664,6 +742,31 @@ fn expand_endpoint_type(
 		));
 	}
 
+	// use the OpenapiFields of the types if they implement that trait
+	#[allow(unused_mut)]
+	let mut fields_fns: Vec<TokenStream> = Vec::new();
+	#[cfg(feature = "openapi")]
+	for (used, fields_fn, ty) in [
+		(
+			has_placeholders.value,
+			"placeholders_fields",
+			&placeholder_ty
+		),
+		(needs_params.value, "params_fields", &params_ty),
+		(needs_body.value, "body_fields", &body_ty)
+	] {
+		if !used {
+			continue;
+		}
+		let fields_fn = format_ident!("{fields_fn}");
+		fields_fns.push(quote! {
+			fn #fields_fn() -> ::std::vec::Vec<::gotham_restful::OpenapiField> {
+				use ::gotham_restful::private::{WithOpenapiFields as _, WithoutOpenapiFields as _};
+				(&::gotham_restful::private::FieldsOf::<#ty>(::core::marker::PhantomData)).openapi_fields()
+			}
+		});
+	}
+
 	let mut handle_args: Vec<TokenStream> = Vec::new();
 	if has_placeholders.value {
 		if matches!(ty, EndpointType::Custom { .. }) {
@@ -743,6 +846,66 @@ fn expand_endpoint_type(
 		Err(err) => err.to_compile_error()
 	};
 
+	// the client method is added to the resource client by `#[derive(ResourceClient)]`
+	let client = cfg!(feature = "client").then(|| {
+		let client_ident = endpoint_client_ident(fun_ident);
+		let mut arg_idents = arg_tys.iter().map(|arg| &arg.ident);
+		let none = quote!(::core::option::Option::None::<&()>);
+		let placeholders = match has_placeholders.value.then(|| arg_idents.next()).flatten() {
+			Some(ident) if matches!(ty, EndpointType::Custom { .. }) => {
+				quote!(::core::option::Option::Some(&#ident))
+			},
+			Some(ident) => quote! {
+				::core::option::Option::Some(&::gotham_restful::private::IdPlaceholder { id: #ident })
+			},
+			None => none.clone()
+		};
+		let params = match needs_params.value.then(|| arg_idents.next()).flatten() {
+			Some(ident) => quote!(::core::option::Option::Some(&#ident)),
+			None => none
+		};
+		let body = match needs_body.value.then(|| arg_idents.next()).flatten() {
+			Some(ident) => quote!(::core::option::Option::Some(#ident)),
+			None => quote!(::core::option::Option::None::<()>)
+		};
+		let client_args = arg_tys.iter().map(|arg| {
+			let ident = &arg.ident;
+			let ty = arg.ty.quote_ty();
+			quote!(#ident: #ty)
+		});
+		let client_attrs = fun
+			.attrs
+			.iter()
+			.filter(|attr| attr.path().is_ident("doc") || attr.path().is_ident("deprecated"));
+		quote! {
+			#[doc(hidden)]
+			#[allow(unused_macros)]
+			macro_rules! #client_ident {
+				($client:ident, [$($vis:tt)*]) => {
+					impl $client {
+						#(#client_attrs)*
+						$($vis)* async fn #fun_ident(&self, #(#client_args),*) -> ::core::result::Result<
+							<#output_ty as ::gotham_restful::client::ClientResponse>::Output,
+							::gotham_restful::client::ClientError
+						> {
+							self.client.send::<#output_ty, _, _, _>(
+								&self.path,
+								#http_method,
+								#uri,
+								#placeholders,
+								#params,
+								#body
+							).await
+						}
+					}
+				};
+			}
+			#[doc(hidden)]
+			#[allow(unused_imports)]
+			pub(crate) use #client_ident;
+		}
+	});
+
 	let tr8 = if cfg!(feature = "openapi") {
 		quote!(::gotham_restful::EndpointWithSchema)
 	} else {
@@ -788,6 +951,7 @@ fn expand_endpoint_type(
 				}
 				#body_typedef
 
+				#[allow(deprecated)]
 				fn handle<'a>(
 					state: &'a mut ::gotham_restful::gotham::state::State,
 					placeholders: Self::Placeholders,
@@ -799,9 +963,16 @@ fn expand_endpoint_type(
 
 				#operation_id
 				#description
+				#tags_fn
+				#deprecated_fn
+				#headers_fn
+				#hidden_fn
+				#(#fields_fns)*
 				#wants_auth
 			}
 		};
+
+		#client
 	};
 	if debug {
 		eprintln!("{code}");
@@ -22,8 +22,13 @@ use from_body::expand_from_body;
 mod request_body;
 use request_body::expand_request_body;
 
+mod openapi_fields;
+use openapi_fields::expand_openapi_fields;
+
 mod resource;
 use resource::expand_resource;
+#[cfg(feature = "client")]
+use resource::expand_resource_client;
 
 mod resource_error;
 use resource_error::expand_resource_error;
@@ -81,6 +86,12 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 	expand_derive(input, expand_resource)
 }
 
+#[cfg(feature = "client")]
+#[proc_macro_derive(ResourceClient, attributes(resource))]
+pub fn derive_resource_client(input: TokenStream) -> TokenStream {
+	expand_derive(input, expand_resource_client)
+}
+
 #[proc_macro_derive(ResourceError, attributes(display, from, status))]
 pub fn derive_resource_error(input: TokenStream) -> TokenStream {
 	expand_derive(input, expand_resource_error)
@@ -149,6 +160,11 @@ pub fn delete(attr: TokenStream, item: TokenStream) -> TokenStream {
 	})
 }
 
+#[proc_macro_attribute]
+pub fn openapi_fields(attr: TokenStream, item: TokenStream) -> TokenStream {
+	expand_macro(attr, item, expand_openapi_fields)
+}
+
 /// PRIVATE MACRO - DO NOT USE
 #[doc(hidden)]
 #[proc_macro_attribute]
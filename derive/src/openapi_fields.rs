@@ -0,0 +1,167 @@
+use crate::{util::ExpectLit, AttributeArgs};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+	parse_quote, punctuated::Punctuated, spanned::Spanned, Attribute, Error, Expr, Fields, Ident,
+	ItemStruct, LitStr, Meta, Result, Token
+};
+
+/// A field that has an example or is deprecated.
+#[cfg_attr(not(feature = "openapi"), allow(dead_code))]
+struct OpenapiField {
+	ident: Ident,
+	rename: Option<LitStr>,
+	example: Option<Expr>,
+	deprecated: bool
+}
+
+fn parse_meta_list(attr: &Attribute) -> Result<Punctuated<Meta, Token![,]>> {
+	attr.meta
+		.require_list()?
+		.parse_args_with(Punctuated::parse_terminated)
+}
+
+/// Find the value of `key = "..."` in `#[serde]` and `#[openapi]` attributes. Unknown serde
+/// attributes are ignored, just like the `OpenapiType` derive does.
+fn find_rename(attrs: &[Attribute], key: &str) -> Result<Option<LitStr>> {
+	let mut rename = None;
+	for attr in attrs {
+		if !attr.path().is_ident("serde") && !attr.path().is_ident("openapi") {
+			continue;
+		}
+		let Ok(metas) = parse_meta_list(attr) else {
+			continue;
+		};
+		for meta in metas {
+			match meta {
+				Meta::NameValue(kv) if kv.path.is_ident(key) => {
+					rename = Some(kv.value.expect_str()?);
+				},
+				_ => {}
+			}
+		}
+	}
+	Ok(rename)
+}
+
+/// Remove `example = ...` from the `#[openapi]` attributes, as the `OpenapiType` derive does not
+/// know about it, and return its value.
+fn take_example(attrs: &mut Vec<Attribute>) -> Result<Option<Expr>> {
+	let mut example = None;
+	let mut kept = Vec::new();
+	for attr in attrs.drain(..) {
+		if !attr.path().is_ident("openapi") {
+			kept.push(attr);
+			continue;
+		}
+		let mut metas = Punctuated::<Meta, Token![,]>::new();
+		for meta in parse_meta_list(&attr)? {
+			match meta {
+				Meta::NameValue(kv) if kv.path.is_ident("example") => {
+					if example.is_some() {
+						return Err(Error::new(
+							kv.path.span(),
+							"`example` must not appear more than once"
+						));
+					}
+					example = Some(kv.value);
+				},
+				meta => metas.push(meta)
+			}
+		}
+		if !metas.is_empty() {
+			kept.push(parse_quote!(#[openapi(#metas)]));
+		}
+	}
+	*attrs = kept;
+	Ok(example)
+}
+
+#[cfg(not(feature = "openapi"))]
+fn impl_openapi_fields(_strukt: &ItemStruct, _fields: Vec<OpenapiField>) -> Result<TokenStream> {
+	Ok(quote!())
+}
+
+#[cfg(feature = "openapi")]
+fn impl_openapi_fields(strukt: &ItemStruct, fields: Vec<OpenapiField>) -> Result<TokenStream> {
+	use serde_derive_internals::attr::RenameRule;
+	use syn::ext::IdentExt as _;
+
+	let rename_all = find_rename(&strukt.attrs, "rename_all")?
+		.map(|rename_all| {
+			RenameRule::from_str(&rename_all.value())
+				.map_err(|_| Error::new(rename_all.span(), "Unknown rename_all rule"))
+		})
+		.transpose()?;
+
+	let fields = fields.into_iter().map(|field| {
+		let name = match (field.rename, rename_all) {
+			(Some(rename), _) => rename.value(),
+			(None, Some(rule)) => rule.apply_to_field(&field.ident.unraw().to_string()),
+			(None, None) => field.ident.unraw().to_string()
+		};
+		let example = match field.example {
+			Some(example) => quote! {
+				::core::option::Option::Some(::gotham_restful::private::serde_json::json!(#example))
+			},
+			None => quote!(::core::option::Option::None)
+		};
+		let deprecated = field.deprecated;
+		quote! {
+			::gotham_restful::OpenapiField {
+				name: #name,
+				example: #example,
+				deprecated: #deprecated
+			}
+		}
+	});
+
+	let ident = &strukt.ident;
+	let (impl_generics, ty_generics, where_clause) = strukt.generics.split_for_impl();
+	Ok(quote! {
+		impl #impl_generics ::gotham_restful::OpenapiFields for #ident #ty_generics #where_clause {
+			fn openapi_fields() -> ::std::vec::Vec<::gotham_restful::OpenapiField> {
+				::std::vec![#(#fields),*]
+			}
+		}
+	})
+}
+
+pub(crate) fn expand_openapi_fields(
+	attrs: AttributeArgs,
+	mut strukt: ItemStruct
+) -> Result<TokenStream> {
+	if let Some(arg) = attrs.0.first() {
+		return Err(Error::new(arg.span(), "Unexpected argument"));
+	}
+	let Fields::Named(named_fields) = &mut strukt.fields else {
+		return Err(Error::new(
+			strukt.fields.span(),
+			"#[openapi_fields] only supports structs with named fields"
+		));
+	};
+
+	let mut fields = Vec::new();
+	for field in &mut named_fields.named {
+		let example = take_example(&mut field.attrs)?;
+		let deprecated = field
+			.attrs
+			.iter()
+			.any(|attr| attr.path().is_ident("deprecated"));
+		if example.is_none() && !deprecated {
+			continue;
+		}
+		fields.push(OpenapiField {
+			ident: field.ident.clone().unwrap_or_else(|| unreachable!()),
+			rename: find_rename(&field.attrs, "rename")?,
+			example,
+			deprecated
+		});
+	}
+
+	let openapi_fields = impl_openapi_fields(&strukt, fields)?;
+	Ok(quote! {
+		#strukt
+		#openapi_fields
+	})
+}
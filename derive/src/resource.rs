@@ -1,15 +1,24 @@
-use crate::{endpoint::endpoint_ident, util::CollectToResult};
+#[cfg(feature = "client")]
+use crate::endpoint::endpoint_client_ident;
+use crate::{
+	endpoint::endpoint_ident,
+	util::{CollectToResult, ExpectLit}
+};
 use either::Either;
 use proc_macro2::{Ident, TokenStream};
+#[cfg(feature = "client")]
+use quote::format_ident;
 use quote::quote;
 use std::iter;
 use syn::{
 	parse::{Parse, ParseStream},
 	punctuated::Punctuated,
-	DeriveInput, Meta, Result, Token
+	spanned::Spanned,
+	Attribute, DeriveInput, Error, Expr, ExprLit, Lit, LitStr, Meta, Path, Result, Token
 };
+use unindent::Unindent;
 
-struct MethodList(Punctuated<Ident, Token![,]>);
+struct MethodList(Punctuated<Meta, Token![,]>);
 
 impl Parse for MethodList {
 	fn parse(input: ParseStream<'_>) -> Result<Self> {
@@ -17,11 +26,44 @@ impl Parse for MethodList {
 	}
 }
 
-pub fn expand_resource(input: DeriveInput) -> Result<TokenStream> {
-	let ident = input.ident;
+enum ResourceItem {
+	Endpoint(Ident),
+	Cors(Path),
+	Tag(LitStr)
+}
+
+impl ResourceItem {
+	fn parse(meta: Meta) -> Result<Self> {
+		match meta {
+			Meta::Path(path) => match path.get_ident() {
+				Some(ident) => Ok(Self::Endpoint(ident.clone())),
+				None => Err(Error::new(path.span(), "Expected endpoint name"))
+			},
+			Meta::NameValue(kv) if kv.path.is_ident("cors") && !cfg!(feature = "cors") => {
+				Err(Error::new(
+					kv.path.span(),
+					"The `cors` feature of gotham_restful is required to use `cors = \"...\"`"
+				))
+			},
+			Meta::NameValue(kv) if kv.path.is_ident("cors") => match kv.value {
+				Expr::Lit(ExprLit {
+					lit: Lit::Str(str), ..
+				}) => Ok(Self::Cors(str.parse()?)),
+				value => Err(Error::new(value.span(), "Expected string literal"))
+			},
+			Meta::NameValue(kv) if kv.path.is_ident("tag") => match kv.value {
+				Expr::Lit(ExprLit {
+					lit: Lit::Str(str), ..
+				}) => Ok(Self::Tag(str)),
+				value => Err(Error::new(value.span(), "Expected string literal"))
+			},
+			meta => Err(Error::new(meta.span(), "Unknown resource attribute"))
+		}
+	}
+}
 
-	let methods = input
-		.attrs
+fn parse_resource_items(attrs: Vec<Attribute>) -> Result<Vec<ResourceItem>> {
+	attrs
 		.into_iter()
 		.filter_map(|attr| match attr.meta {
 			Meta::List(list) if list.path.is_ident("resource") => Some(list.tokens),
@@ -29,13 +71,66 @@ pub fn expand_resource(input: DeriveInput) -> Result<TokenStream> {
 		})
 		.map(|tokens| syn::parse2(tokens).map(|m: MethodList| m.0.into_iter()))
 		.flat_map(|list| match list {
-			Ok(iter) => Either::Left(iter.map(|method| {
-				let ident = endpoint_ident(&method);
-				Ok(quote!(route.endpoint::<#ident>();))
-			})),
+			Ok(iter) => Either::Left(iter.map(ResourceItem::parse)),
 			Err(err) => Either::Right(iter::once(Err(err)))
 		})
-		.collect_to_result()?;
+		.collect_to_result()
+}
+
+pub fn expand_resource(input: DeriveInput) -> Result<TokenStream> {
+	let ident = input.ident;
+
+	// extract the documentation
+	let mut doc: Vec<String> = vec![String::new()];
+	for attr in &input.attrs {
+		match &attr.meta {
+			Meta::NameValue(kv) if kv.path.is_ident("doc") => {
+				doc.push(kv.value.clone().expect_str()?.value())
+			},
+			_ => {}
+		}
+	}
+	let doc = doc.join("\n").unindent();
+	#[cfg_attr(not(feature = "openapi"), allow(unused_variables))]
+	let doc = doc.trim();
+
+	let items = parse_resource_items(input.attrs)?;
+
+	// the cors config needs to be set before registering any endpoint
+	let cors = items.iter().filter_map(|item| match item {
+		ResourceItem::Cors(path) => Some(quote!(route.cors_config(#path());)),
+		_ => None
+	});
+	let endpoints = items.iter().filter_map(|item| match item {
+		ResourceItem::Endpoint(method) => {
+			let ident = endpoint_ident(method);
+			Some(quote!(route.endpoint::<#ident>();))
+		},
+		_ => None
+	});
+	let methods: Vec<TokenStream> = cors.chain(endpoints).collect();
+
+	// the tag defaults to the name of the resource, without the `Resource` suffix
+	#[cfg_attr(not(feature = "openapi"), allow(unused_variables))]
+	let tag = items
+		.iter()
+		.rev()
+		.find_map(|item| match item {
+			ResourceItem::Tag(tag) => Some(tag.value()),
+			_ => None
+		})
+		.unwrap_or_else(|| {
+			let name = ident.to_string();
+			match name.strip_suffix("Resource") {
+				Some(name) if !name.is_empty() => name.to_owned(),
+				_ => name
+			}
+		});
+	#[cfg_attr(not(feature = "openapi"), allow(unused_variables))]
+	let tag_description = match doc.is_empty() {
+		true => quote!(::core::option::Option::None),
+		false => quote!(::core::option::Option::Some(#doc))
+	};
 
 	let non_openapi_impl = quote! {
 		impl ::gotham_restful::Resource for #ident {
@@ -50,6 +145,7 @@ pub fn expand_resource(input: DeriveInput) -> Result<TokenStream> {
 		Some(quote! {
 			impl ::gotham_restful::ResourceWithSchema for #ident {
 				fn setup<D: ::gotham_restful::DrawResourceRoutesWithSchema>(mut route: D) {
+					route.tag(#tag, #tag_description);
 					#(#methods)*
 				}
 			}
@@ -60,3 +156,44 @@ pub fn expand_resource(input: DeriveInput) -> Result<TokenStream> {
 		#openapi_impl
 	})
 }
+
+#[cfg(feature = "client")]
+pub fn expand_resource_client(input: DeriveInput) -> Result<TokenStream> {
+	let ident = input.ident;
+	let vis = input.vis;
+	let client_ident = format_ident!("{ident}Client");
+	let doc = format!("A typed client for the endpoints of [`{ident}`].");
+
+	let endpoints = parse_resource_items(input.attrs)?
+		.into_iter()
+		.filter_map(|item| match item {
+			ResourceItem::Endpoint(method) => {
+				let macro_ident = endpoint_client_ident(&method);
+				// the client methods are as visible as the client, not as the endpoint functions
+				Some(quote!(#macro_ident!(#client_ident, [#vis]);))
+			},
+			_ => None
+		});
+
+	Ok(quote! {
+		#[doc = #doc]
+		#[derive(::core::clone::Clone, ::core::fmt::Debug)]
+		#vis struct #client_ident {
+			client: ::gotham_restful::client::Client,
+			path: ::std::string::String
+		}
+
+		impl #client_ident {
+			/// Create a new client for the resource that was registered at `path`, relative to
+			/// the base url of `client`.
+			#vis fn new(client: ::gotham_restful::client::Client, path: &::core::primitive::str) -> Self {
+				Self {
+					client,
+					path: ::std::string::String::from(path)
+				}
+			}
+		}
+
+		#(#endpoints)*
+	})
+}
@@ -168,6 +168,20 @@ impl ErrorVariant {
 		})
 	}
 
+	/// The display string of this variant if it does not contain any format arguments, so that
+	/// it can be used as the description of the error response.
+	fn static_display(&self) -> Option<String> {
+		let display = self.display.as_ref()?.value();
+		if display
+			.replace("{{", "")
+			.replace("}}", "")
+			.contains(['{', '}'])
+		{
+			return None;
+		}
+		Some(display.replace("{{", "{").replace("}}", "}"))
+	}
+
 	fn status(&self) -> Option<TokenStream> {
 		self.status.as_ref().map(|status| {
 			// the status might be relative to StatusCode, so let's fix that
@@ -367,6 +381,62 @@ pub fn expand_resource_error(input: DeriveInput) -> Result<TokenStream> {
 		None
 	};
 
+	let headers = if cfg!(feature = "openapi") {
+		let headers = variants.iter().filter(|v| v.status().is_none()).map(|v| {
+			// we would've errored before if from_ty was not set
+			let from_ty = &v.from_ty.as_ref().unwrap().1;
+			quote! {
+				if <#from_ty as ::gotham_restful::IntoResponseError>::status_codes().contains(&code) {
+					headers.extend(<#from_ty as ::gotham_restful::IntoResponseError>::headers(code));
+				}
+			}
+		});
+		Some(quote! {
+			fn headers(code: ::gotham_restful::gotham::hyper::StatusCode) -> ::std::vec::Vec<::gotham_restful::ResponseHeader> {
+				let mut headers = <::std::vec::Vec<::gotham_restful::ResponseHeader>>::new();
+				#(#headers)*
+				headers
+			}
+		})
+	} else {
+		None
+	};
+
+	let description = if cfg!(feature = "openapi") {
+		let descriptions = variants.iter().map(|v| match v.status() {
+			Some(code) => match v.static_display() {
+				Some(display) => quote! {
+					if code == #code {
+						descriptions.push(::std::string::String::from(#display));
+					}
+				},
+				None => quote!()
+			},
+			None => {
+				// we would've errored before if from_ty was not set
+				let from_ty = &v.from_ty.as_ref().unwrap().1;
+				quote! {
+					if <#from_ty as ::gotham_restful::IntoResponseError>::status_codes().contains(&code) {
+						descriptions.extend(<#from_ty as ::gotham_restful::IntoResponseError>::description(code));
+					}
+				}
+			}
+		});
+		Some(quote! {
+			fn description(code: ::gotham_restful::gotham::hyper::StatusCode) -> ::core::option::Option<::std::string::String> {
+				let mut descriptions = <::std::vec::Vec<::std::string::String>>::new();
+				#(#descriptions)*
+				if descriptions.is_empty() {
+					::core::option::Option::None
+				} else {
+					::core::option::Option::Some(descriptions.join("\n\n"))
+				}
+			}
+		})
+	} else {
+		None
+	};
+
 	let were = variants
 		.iter()
 		.filter_map(|variant| variant.were())
@@ -393,6 +463,8 @@ pub fn expand_resource_error(input: DeriveInput) -> Result<TokenStream> {
 
 			#status_codes
 			#schema
+			#headers
+			#description
 		}
 
 		#( #from_impls )*
@@ -24,10 +24,11 @@ impl Redoc {
 				"default-src 'none';",
 				"base-uri 'none';",
 				"script-src 'unsafe-inline' 'sha256-{}' 'strict-dynamic';",
-				"style-src 'unsafe-inline' https://fonts.googleapis.com;",
-				"font-src https://fonts.gstatic.com;",
-				"connet-src 'self';",
-				"img-src blob: data:;"
+				"style-src 'unsafe-inline';",
+				"connect-src 'self';",
+				"img-src blob: data:;",
+				"form-action 'none';",
+				"frame-ancestors 'none';"
 			),
 			self.script_hash
 		)
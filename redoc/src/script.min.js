@@ -1 +1 @@
-(()=>{"use strict";function e(e,o){const n=document.createElement(e);for(const e in o)n.setAttribute(e,o[e]);return n}const o=document.head;o.appendChild(e("link",{rel:"stylesheet",href:"https://fonts.googleapis.com/css?family=Open+Sans:300,400,700|Source+Code+Pro:300,400,700&display=swap"}));const n=e("script",{src:"https://cdn.redoc.ly/redoc/v2.5.3/bundles/redoc.standalone.js",integrity:"sha512-qvBFYTqc2cW6IcK+smxCrHVwP6q9c6rXOWWadH5be4qs1lXPHoZ24xTdY6rk6Kf5Wu+L/xoP6VbkJoPP+KyHEQ==",crossOrigin:"anonymous"});n.addEventListener("load",(function(){var e;const o=document.getElementById("spec"),n=document.getElementById("redoc");if(null===o||null===n)return void console.error("Unable to find HTML elements");const t=JSON.parse(null!==(e=o.textContent)&&void 0!==e?e:""),s="#262a2b",r="#fafafa",a={expandResponses:"200",onlyRequiredInSamples:!0,theme:{typography:{fontFamily:'"Open Sans",sans-serif',fontWeightBold:"700",headings:{fontFamily:'"Open Sans",sans-serif'},code:{fontFamily:'"Source Code Pro",monospace'}},sidebar:{width:"20rem",backgroundColor:s,textColor:r},rightPanel:{backgroundColor:s,textColor:r},fab:{backgroundColor:s,textColor:r}}};Redoc.init(t,a,n)})),o.appendChild(n)})();
\ No newline at end of file
+(()=>{"use strict";function e(e,o){const n=document.createElement(e);for(const e in o)n.setAttribute(e,o[e]);return n}const o=document.head,n=e("script",{src:"https://cdn.redoc.ly/redoc/v2.5.3/bundles/redoc.standalone.js",integrity:"sha512-qvBFYTqc2cW6IcK+smxCrHVwP6q9c6rXOWWadH5be4qs1lXPHoZ24xTdY6rk6Kf5Wu+L/xoP6VbkJoPP+KyHEQ==",crossOrigin:"anonymous"});n.addEventListener("load",(function(){var e;const o=document.getElementById("spec"),n=document.getElementById("redoc");if(null===o||null===n)return void console.error("Unable to find HTML elements");const t=JSON.parse(null!==(e=o.textContent)&&void 0!==e?e:""),s="#262a2b",r="#fafafa",a='-apple-system,BlinkMacSystemFont,"Segoe UI",Roboto,"Helvetica Neue",Arial,sans-serif',i={expandResponses:"200",onlyRequiredInSamples:!0,theme:{typography:{fontFamily:a,fontWeightBold:"700",headings:{fontFamily:a},code:{fontFamily:'ui-monospace,SFMono-Regular,Menlo,Consolas,"Liberation Mono",monospace'}},sidebar:{width:"20rem",backgroundColor:s,textColor:r},rightPanel:{backgroundColor:s,textColor:r},fab:{backgroundColor:s,textColor:r}}};Redoc.init(t,i,n)})),o.appendChild(n)})();
\ No newline at end of file
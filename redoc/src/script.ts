@@ -14,18 +14,20 @@ function initRedoc() {
 	const spec = JSON.parse(specElem.textContent ?? "");
 	const bgColor = '#262a2b';
 	const fgColor = '#fafafa';
+	// use the system fonts so that no fonts need to be loaded from a third party
+	const fontFamily = '-apple-system,BlinkMacSystemFont,"Segoe UI",Roboto,"Helvetica Neue",Arial,sans-serif';
 	const options = {
 		expandResponses: "200",
 		onlyRequiredInSamples: true,
 		theme: {
 			typography: {
-				fontFamily: '"Open Sans",sans-serif',
+				fontFamily,
 				fontWeightBold: '700',
 				headings: {
-					fontFamily: '"Open Sans",sans-serif',
+					fontFamily,
 				},
 				code: {
-					fontFamily: '"Source Code Pro",monospace'
+					fontFamily: 'ui-monospace,SFMono-Regular,Menlo,Consolas,"Liberation Mono",monospace'
 				}
 			},
 			sidebar: {
@@ -55,10 +57,6 @@ function createElement(tag: string, attrs: { [key: string]: string }): HTMLEleme
 }
 
 const head = document.head;
-head.appendChild(createElement("link", {
-	rel: "stylesheet",
-	href: "https://fonts.googleapis.com/css?family=Open+Sans:300,400,700|Source+Code+Pro:300,400,700&display=swap"
-}));
 const script = createElement("script", {
 	src: REDOC_URL,
 	integrity: `sha512-${REDOC_SRI}`,
@@ -1,4 +1,4 @@
-use crate::AuthError;
+use crate::{AuthError, AuthFailure};
 
 use base64::prelude::*;
 use futures_util::{
@@ -9,24 +9,33 @@ use gotham::{
 	anyhow,
 	cookie::CookieJar,
 	handler::HandlerFuture,
-	hyper::header::{HeaderMap, HeaderName, AUTHORIZATION},
+	hyper::{
+		header::{HeaderMap, HeaderName, AUTHORIZATION},
+		Uri
+	},
 	middleware::{cookie::CookieParser, Middleware, NewMiddleware},
 	prelude::*,
 	state::State
 };
-use jsonwebtoken::DecodingKey;
+use jsonwebtoken::{errors::ErrorKind, DecodingKey};
+use percent_encoding::percent_decode_str;
 use serde::de::DeserializeOwned;
-use std::{marker::PhantomData, panic::RefUnwindSafe, pin::Pin};
+use std::{cell::Cell, marker::PhantomData, panic::RefUnwindSafe, pin::Pin};
 
 pub type AuthValidation = jsonwebtoken::Validation;
 
 /// The authentication status returned by the auth middleware for each request.
 #[derive(Debug, StateData)]
+#[non_exhaustive]
 pub enum AuthStatus<T: Send + 'static> {
 	/// The auth status is unknown. This is likely because no secret was provided
 	/// that could be used to verify the token of the client.
 	Unknown,
 
+	/// The [AuthHandler] looked at the claims of the token, but did not provide a
+	/// secret to verify it, e.g. because the token was signed with an unknown key.
+	Declined,
+
 	/// The request has been performed without any kind of authentication.
 	Unauthenticated,
 
@@ -35,6 +44,10 @@ pub enum AuthStatus<T: Send + 'static> {
 	/// included error.
 	Invalid(jsonwebtoken::errors::Error),
 
+	/// The request has been performed with a valid token that has been revoked
+	/// by the [AuthHandler].
+	Revoked,
+
 	/// The request has been performed with a valid authentication. The claims
 	/// that were decoded from the token are attached.
 	Authenticated(T)
@@ -48,19 +61,54 @@ where
 		// TODO why is this manually implemented?
 		match self {
 			Self::Unknown => Self::Unknown,
+			Self::Declined => Self::Declined,
 			Self::Unauthenticated => Self::Unauthenticated,
 			Self::Invalid(err) => Self::Invalid(err.clone()),
+			Self::Revoked => Self::Revoked,
 			Self::Authenticated(data) => Self::Authenticated(data.clone())
 		}
 	}
 }
 
+fn jwt_failure(err: &jsonwebtoken::errors::Error) -> AuthFailure {
+	match err.kind() {
+		ErrorKind::InvalidToken
+		| ErrorKind::MissingRequiredClaim(_)
+		| ErrorKind::Base64(_)
+		| ErrorKind::Json(_)
+		| ErrorKind::Utf8(_) => AuthFailure::Malformed,
+		ErrorKind::InvalidSignature | ErrorKind::InvalidAlgorithm => AuthFailure::BadSignature,
+		ErrorKind::ExpiredSignature => AuthFailure::Expired,
+		ErrorKind::ImmatureSignature => AuthFailure::NotYetValid,
+		ErrorKind::InvalidAudience => AuthFailure::WrongAudience,
+		ErrorKind::InvalidIssuer => AuthFailure::WrongIssuer,
+		_ => AuthFailure::Invalid
+	}
+}
+
 impl<T: Send + 'static> AuthStatus<T> {
+	/// Return the reason why the request could not be authenticated, or `None` if it was.
+	pub fn failure(&self) -> Option<AuthFailure> {
+		match self {
+			Self::Unknown => Some(AuthFailure::NoSecret),
+			Self::Declined => Some(AuthFailure::UnknownKey),
+			Self::Unauthenticated => Some(AuthFailure::MissingToken),
+			Self::Invalid(err) => Some(jwt_failure(err)),
+			Self::Revoked => Some(AuthFailure::Revoked),
+			Self::Authenticated(_) => None
+		}
+	}
+
 	pub fn ok(self) -> Result<T, AuthError> {
 		match self {
-			Self::Unknown => Err(AuthError::new("The authentication could not be determined")),
-			Self::Unauthenticated => Err(AuthError::new("Missing token")),
-			Self::Invalid(err) => Err(AuthError::new(format!("Invalid token: {err}"))),
+			Self::Unknown => Err(AuthFailure::NoSecret.into()),
+			Self::Declined => Err(AuthFailure::UnknownKey.into()),
+			Self::Unauthenticated => Err(AuthFailure::MissingToken.into()),
+			Self::Invalid(err) => Err(AuthError::with_failure(
+				jwt_failure(&err),
+				format!("Invalid token: {err}")
+			)),
+			Self::Revoked => Err(AuthFailure::Revoked.into()),
 			Self::Authenticated(data) => Ok(data)
 		}
 	}
@@ -76,7 +124,29 @@ pub enum AuthSource {
 	/// Take the token from the HTTP Authorization header. This is different from `Header("Authorization")`
 	/// as it will follow the `scheme param` format from the HTTP specification. The `scheme` will
 	/// be discarded, so its value doesn't matter.
-	AuthorizationHeader
+	AuthorizationHeader,
+	/// Take the token from a query parameter with the given name. This is useful for clients like
+	/// the browser's `EventSource` or `WebSocket` that cannot set custom headers. The parameter is
+	/// left in the query string, so make sure your endpoint's `Params` type doesn't reject unknown
+	/// fields.
+	QueryParam(String)
+}
+
+/// Find the value of the first query parameter called `name` in the (still encoded) `query`.
+fn query_param(query: &str, name: &str) -> Option<String> {
+	let decode = |value: &str| {
+		let value = value.replace('+', " ");
+		percent_decode_str(&value)
+			.decode_utf8()
+			.ok()
+			.map(|value| value.into_owned())
+	};
+
+	query
+		.split('&')
+		.map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+		.find(|(key, _)| decode(key).as_deref() == Some(name))
+		.and_then(|(_, value)| decode(value))
 }
 
 /// This trait will help the auth middleware to determine the validity of an authentication token.
@@ -106,6 +176,12 @@ pub trait AuthHandler<Data> {
 		state: &mut State,
 		decode_data: F
 	) -> Option<Vec<u8>>;
+
+	/// Return whether the token with the given claims has been revoked. This is only called
+	/// for tokens that were successfully verified. Returns `false` by default.
+	fn is_revoked(&self, _state: &mut State, _data: &Data) -> bool {
+		false
+	}
 }
 
 /// An [AuthHandler] returning always the same secret. See [AuthMiddleware] for a usage example.
@@ -242,7 +318,10 @@ where
 				.and_then(|map| map.get(AUTHORIZATION))
 				.and_then(|header| header.to_str().ok())
 				.and_then(|value| value.split_whitespace().nth(1))
-				.map(|value| value.to_owned())
+				.map(|value| value.to_owned()),
+			AuthSource::QueryParam(name) => Uri::try_borrow_from(state)
+				.and_then(|uri| uri.query())
+				.and_then(|query| query_param(query, name))
 		};
 
 		// unauthed if no token
@@ -252,16 +331,19 @@ where
 		};
 
 		// get the secret from the handler, possibly decoding claims ourselves
+		let decoded = Cell::new(None);
 		let secret = self.handler.jwt_secret(state, || {
-			let b64 = token.split('.').nth(1)?;
-			let raw = BASE64_URL_SAFE_NO_PAD.decode(b64).ok()?;
-			serde_json::from_slice(&raw).ok()?
+			let data = decode_claims(&token);
+			decoded.set(Some(data.is_some()));
+			data
 		});
 
-		// unknown if no secret
-		let secret = match secret {
-			Some(secret) => secret,
-			None => return AuthStatus::Unknown
+		// without a secret, tell apart why the handler didn't provide one
+		let secret = match (secret, decoded.get()) {
+			(Some(secret), _) => secret,
+			(None, Some(false)) => return AuthStatus::Invalid(ErrorKind::InvalidToken.into()),
+			(None, Some(true)) => return AuthStatus::Declined,
+			(None, None) => return AuthStatus::Unknown
 		};
 
 		// validate the token
@@ -274,11 +356,32 @@ where
 			Err(e) => return AuthStatus::Invalid(e)
 		};
 
+		// check that the token was not revoked
+		if self.handler.is_revoked(state, &data) {
+			return AuthStatus::Revoked;
+		}
+
 		// we found a valid token
 		AuthStatus::Authenticated(data)
 	}
 }
 
+/// Decode the claims of `token` without verifying its signature.
+fn decode_claims<Data: DeserializeOwned>(token: &str) -> Option<Data> {
+	let b64 = token.split('.').nth(1)?;
+	let raw = BASE64_URL_SAFE_NO_PAD.decode(b64).ok()?;
+	serde_json::from_slice(&raw).ok()?
+}
+
+/// Put the status and, if not authenticated, the failure into the state.
+pub(crate) fn put_status<T: Send + 'static>(state: &mut State, status: AuthStatus<T>) {
+	if let Some(failure) = status.failure() {
+		debug!("Authentication failed: {}", failure.code());
+		state.put(failure);
+	}
+	state.put(status);
+}
+
 impl<Data, Handler> Middleware for AuthMiddleware<Data, Handler>
 where
 	Data: DeserializeOwned + Send + 'static,
@@ -293,7 +396,7 @@ where
 
 		// put the status in our state
 		let status = self.auth_status(&mut state);
-		state.put(status);
+		put_status(&mut state, status);
 
 		// call the rest of the chain
 		chain(state)
@@ -317,7 +420,11 @@ where
 #[cfg(test)]
 mod test {
 	use super::*;
-	use gotham::{cookie::Cookie, hyper::header::COOKIE};
+	use crate::IntoResponseError;
+	use gotham::{
+		cookie::Cookie,
+		hyper::{header::COOKIE, StatusCode}
+	};
 	use jsonwebtoken::errors::ErrorKind;
 	use std::fmt::Debug;
 
@@ -372,10 +479,43 @@ mod test {
 				format!("Bearer {VALID_TOKEN}").parse().unwrap()
 			);
 			state.put(headers);
-			middleware.auth_status(state);
+			let status = middleware.auth_status(state);
+			assert_eq!(status.failure(), Some(AuthFailure::NoSecret));
 		});
 	}
 
+	#[derive(Default)]
+	struct DecliningAuthHandler;
+	impl<T> AuthHandler<T> for DecliningAuthHandler {
+		fn jwt_secret<F: FnOnce() -> Option<T>>(
+			&self,
+			_state: &mut State,
+			decode_data: F
+		) -> Option<Vec<u8>> {
+			decode_data();
+			None
+		}
+	}
+
+	#[test]
+	fn test_auth_middleware_declined() {
+		let middleware = <AuthMiddleware<TestData, DecliningAuthHandler>>::from_source(
+			AuthSource::AuthorizationHeader
+		);
+		for (token, failure) in [
+			(VALID_TOKEN, AuthFailure::UnknownKey),
+			("eyJhbGciOiJIUzI1NiJ9.bm9wZQ.c2ln", AuthFailure::Malformed)
+		] {
+			State::with_new(|state| {
+				let mut headers = HeaderMap::new();
+				headers.insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+				state.put(headers);
+				let status = middleware.auth_status(state);
+				assert_eq!(status.failure(), Some(failure));
+			});
+		}
+	}
+
 	#[derive(Default)]
 	struct TestAssertingHandler;
 	impl<T> AuthHandler<T> for TestAssertingHandler
@@ -408,6 +548,55 @@ mod test {
 		});
 	}
 
+	struct RevokingAuthHandler;
+	impl<T> AuthHandler<T> for RevokingAuthHandler {
+		fn jwt_secret<F: FnOnce() -> Option<T>>(
+			&self,
+			_state: &mut State,
+			_decode_data: F
+		) -> Option<Vec<u8>> {
+			Some(JWT_SECRET.to_vec())
+		}
+
+		fn is_revoked(&self, _state: &mut State, _data: &T) -> bool {
+			true
+		}
+	}
+
+	#[test]
+	fn test_auth_middleware_revoked_token() {
+		let middleware = AuthMiddleware::<TestData, _>::new(
+			AuthSource::AuthorizationHeader,
+			Default::default(),
+			RevokingAuthHandler
+		);
+		State::with_new(|state| {
+			let mut headers = HeaderMap::new();
+			headers.insert(
+				AUTHORIZATION,
+				format!("Bearer {VALID_TOKEN}").parse().unwrap()
+			);
+			state.put(headers);
+			let status = middleware.auth_status(state);
+			match status {
+				AuthStatus::Revoked => {},
+				_ => panic!("Expected AuthStatus::Revoked, got {status:?}")
+			};
+		});
+	}
+
+	#[test]
+	fn test_auth_failure_response() {
+		let err = AuthStatus::<TestData>::Unknown.ok().unwrap_err();
+		assert_eq!(err.failure(), Some(AuthFailure::NoSecret));
+		let res = err.into_response_error().unwrap();
+		assert_eq!(res.status(), StatusCode::FORBIDDEN);
+		assert_eq!(
+			res.full_body().unwrap(),
+			br#"{"error":true,"message":"The authentication could not be determined","code":"no_secret"}"#
+		);
+	}
+
 	fn new_middleware<T>(source: AuthSource) -> AuthMiddleware<T, StaticAuthHandler>
 	where
 		T: DeserializeOwned + Send
@@ -424,6 +613,7 @@ mod test {
 		let middleware = new_middleware::<TestData>(AuthSource::AuthorizationHeader);
 		State::with_new(|state| {
 			let status = middleware.auth_status(state);
+			assert_eq!(status.failure(), Some(AuthFailure::MissingToken));
 			match status {
 				AuthStatus::Unauthenticated => {},
 				_ => panic!("Expected AuthStatus::Unauthenticated, got {status:?}")
@@ -442,6 +632,7 @@ mod test {
 			);
 			state.put(headers);
 			let status = middleware.auth_status(state);
+			assert_eq!(status.failure(), Some(AuthFailure::Expired));
 			match status {
 				AuthStatus::Invalid(err) if *err.kind() == ErrorKind::ExpiredSignature => {},
 				_ => panic!(
@@ -462,6 +653,7 @@ mod test {
 			);
 			state.put(headers);
 			let status = middleware.auth_status(state);
+			assert_eq!(status.failure(), Some(AuthFailure::Malformed));
 			match status {
 				AuthStatus::Invalid(err) if *err.kind() == ErrorKind::InvalidToken => {},
 				_ => panic!(
@@ -506,6 +698,35 @@ mod test {
 		})
 	}
 
+	#[test]
+	fn test_auth_middleware_query_param_token() {
+		let param_name = "znoiprwmvfexju";
+		let middleware = new_middleware::<TestData>(AuthSource::QueryParam(param_name.to_owned()));
+		State::with_new(|state| {
+			let uri: Uri = format!("/foo?bar=baz&{param_name}={VALID_TOKEN}")
+				.parse()
+				.unwrap();
+			state.put(uri);
+			let status = middleware.auth_status(state);
+			match status {
+				AuthStatus::Authenticated(data) => assert_eq!(data, TestData::default()),
+				_ => panic!("Expected AuthStatus::Authenticated, got {status:?}")
+			};
+		})
+	}
+
+	#[test]
+	fn test_query_param() {
+		assert_eq!(query_param("a=1&b=2", "b").as_deref(), Some("2"));
+		assert_eq!(query_param("a=1&a=2", "a").as_deref(), Some("1"));
+		assert_eq!(
+			query_param("a%5Bb%5D=x+y%2Bz", "a[b]").as_deref(),
+			Some("x y+z")
+		);
+		assert_eq!(query_param("a&b=2", "a").as_deref(), Some(""));
+		assert_eq!(query_param("a=1", "b"), None);
+	}
+
 	#[test]
 	fn test_auth_middleware_cookie_token() {
 		let cookie_name = "znoiprwmvfexju";
@@ -0,0 +1,375 @@
+//! A typed client for resources, generated by `#[derive(ResourceClient)]`.
+
+use crate::{NoContent, Raw, Redirect, Response, Success};
+use futures_util::future::Future;
+use gotham::{
+	hyper::{
+		body::{to_bytes, Bytes},
+		client::HttpConnector,
+		header::{HeaderName, HeaderValue, CONTENT_TYPE, LOCATION},
+		http, Body, HeaderMap, Method, Request, StatusCode
+	},
+	mime::{Mime, APPLICATION_JSON, APPLICATION_OCTET_STREAM}
+};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::pin::Pin;
+use thiserror::Error;
+
+/// The characters that are not percent-encoded in path segments and query strings.
+const ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+	.remove(b'-')
+	.remove(b'.')
+	.remove(b'_')
+	.remove(b'~');
+
+/// An error that occurred while calling an endpoint.
+#[derive(Debug, Error)]
+pub enum ClientError {
+	/// The request could not be built, e.g. because the uri is invalid.
+	#[error("Invalid request: {0}")]
+	Request(#[from] http::Error),
+	/// The placeholders, parameters or body of the request could not be serialized.
+	#[error("Unable to serialize the request: {0}")]
+	Serialize(String),
+	/// The request could not be sent or the response could not be received.
+	#[error("HTTP error: {0}")]
+	Http(#[from] gotham::hyper::Error),
+	/// The server answered with a status code that does not indicate success, e.g. because the
+	/// endpoint returned an error.
+	#[error("The server responded with {status}")]
+	Status { status: StatusCode, body: Bytes },
+	/// The response could not be turned into the output type of the endpoint.
+	#[error("Unable to deserialize the response: {0}")]
+	Deserialize(String)
+}
+
+impl ClientError {
+	fn serialize(err: impl ToString) -> Self {
+		Self::Serialize(err.to_string())
+	}
+
+	fn deserialize(err: impl ToString) -> Self {
+		Self::Deserialize(err.to_string())
+	}
+}
+
+/// This trait turns the response of an endpoint back into the value that the client returns.
+/// It is implemented for all response types of this crate.
+pub trait ClientResponse {
+	/// The value returned by the client if the request was successful.
+	type Output;
+
+	/// Turn the response into [Self::Output].
+	fn from_response(res: http::Response<Bytes>) -> Result<Self::Output, ClientError>;
+}
+
+fn check_status(res: &http::Response<Bytes>) -> Result<(), ClientError> {
+	if res.status().is_success() {
+		return Ok(());
+	}
+	Err(ClientError::Status {
+		status: res.status(),
+		body: res.body().clone()
+	})
+}
+
+fn content_type(res: &http::Response<Bytes>) -> Option<Mime> {
+	res.headers()
+		.get(CONTENT_TYPE)
+		.and_then(|mime| mime.to_str().ok())
+		.and_then(|mime| mime.parse().ok())
+}
+
+impl ClientResponse for Response {
+	type Output = Response;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Response, ClientError> {
+		let mime = content_type(&res);
+		let (parts, body) = res.into_parts();
+		let mut res = Response::new(parts.status, body, mime);
+		res.headers = parts.headers;
+		Ok(res)
+	}
+}
+
+impl<T: DeserializeOwned> ClientResponse for Success<T> {
+	type Output = T;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<T, ClientError> {
+		check_status(&res)?;
+		serde_json::from_slice(res.body()).map_err(ClientError::deserialize)
+	}
+}
+
+impl<R: DeserializeOwned, E> ClientResponse for Result<R, E> {
+	type Output = R;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<R, ClientError> {
+		Success::<R>::from_response(res)
+	}
+}
+
+impl ClientResponse for NoContent {
+	type Output = ();
+
+	fn from_response(res: http::Response<Bytes>) -> Result<(), ClientError> {
+		check_status(&res)
+	}
+}
+
+impl<E> ClientResponse for Result<NoContent, E> {
+	type Output = ();
+
+	fn from_response(res: http::Response<Bytes>) -> Result<(), ClientError> {
+		NoContent::from_response(res)
+	}
+}
+
+impl<T> ClientResponse for Raw<T> {
+	type Output = Raw<Vec<u8>>;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Raw<Vec<u8>>, ClientError> {
+		check_status(&res)?;
+		let mime = content_type(&res).unwrap_or(APPLICATION_OCTET_STREAM);
+		Ok(Raw::new(res.into_body().to_vec(), mime))
+	}
+}
+
+impl<T, E> ClientResponse for Result<Raw<T>, E> {
+	type Output = Raw<Vec<u8>>;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Raw<Vec<u8>>, ClientError> {
+		Raw::<T>::from_response(res)
+	}
+}
+
+impl ClientResponse for Redirect {
+	type Output = Redirect;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Redirect, ClientError> {
+		if !res.status().is_redirection() {
+			check_status(&res)?;
+		}
+		let to = res
+			.headers()
+			.get(LOCATION)
+			.ok_or_else(|| ClientError::deserialize("Missing Location header"))?
+			.to_str()
+			.map_err(ClientError::deserialize)?;
+		Ok(Redirect { to: to.to_owned() })
+	}
+}
+
+impl<E> ClientResponse for Result<Redirect, E> {
+	type Output = Redirect;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Redirect, ClientError> {
+		Redirect::from_response(res)
+	}
+}
+
+impl<Res: ClientResponse> ClientResponse for Pin<Box<dyn Future<Output = Res> + Send>> {
+	type Output = Res::Output;
+
+	fn from_response(res: http::Response<Bytes>) -> Result<Res::Output, ClientError> {
+		Res::from_response(res)
+	}
+}
+
+/// This trait turns the body argument of an endpoint into a request body. It is implemented for
+/// all serializable types, which are sent as json, and for [Raw].
+pub trait ClientRequestBody {
+	/// Return the media type and the content of the request body.
+	fn into_request_body(self) -> Result<(Mime, Body), ClientError>;
+}
+
+impl<T: Serialize> ClientRequestBody for T {
+	fn into_request_body(self) -> Result<(Mime, Body), ClientError> {
+		let json = serde_json::to_vec(&self).map_err(ClientError::serialize)?;
+		Ok((APPLICATION_JSON, json.into()))
+	}
+}
+
+impl<T: Into<Body>> ClientRequestBody for Raw<T> {
+	fn into_request_body(self) -> Result<(Mime, Body), ClientError> {
+		Ok((self.mime, self.raw.into()))
+	}
+}
+
+fn to_object<T: Serialize + ?Sized>(
+	value: &T
+) -> Result<serde_json::Map<String, Value>, ClientError> {
+	match serde_json::to_value(value).map_err(ClientError::serialize)? {
+		Value::Object(obj) => Ok(obj),
+		Value::Null => Ok(Default::default()),
+		_ => Err(ClientError::serialize("Expected a struct"))
+	}
+}
+
+fn encode_value(value: &Value) -> String {
+	let value = match value {
+		Value::String(str) => str.clone(),
+		value => value.to_string()
+	};
+	utf8_percent_encode(&value, ENCODE_SET).to_string()
+}
+
+/// Replace the placeholders in `uri`, which is in gotham's format, with their values.
+fn fill_placeholders<P>(uri: &str, placeholders: Option<&P>) -> Result<String, ClientError>
+where
+	P: Serialize + ?Sized
+{
+	let values = placeholders.map(to_object).transpose()?.unwrap_or_default();
+	uri.split('/')
+		.filter(|segment| !segment.is_empty())
+		.map(|segment| match segment.strip_prefix(':') {
+			Some(name) => {
+				let name = name.split(':').next().unwrap_or(name);
+				values
+					.get(name)
+					.map(encode_value)
+					.ok_or_else(|| ClientError::serialize(format!("Missing placeholder {name}")))
+			},
+			None => Ok(segment.to_owned())
+		})
+		.collect::<Result<Vec<_>, _>>()
+		.map(|segments| segments.join("/"))
+}
+
+/// Serialize `params` into a query string. Sequences are repeated for every item, which is what
+/// gotham's query string extractor expects.
+fn query_string<Q: Serialize>(params: &Q) -> Result<String, ClientError> {
+	let mut query = Vec::new();
+	for (name, value) in to_object(params)? {
+		let name = utf8_percent_encode(&name, ENCODE_SET);
+		match value {
+			Value::Null => {},
+			Value::Array(values) => {
+				for value in values {
+					query.push(format!("{name}={}", encode_value(&value)));
+				}
+			},
+			value => query.push(format!("{name}={}", encode_value(&value)))
+		}
+	}
+	Ok(query.join("&"))
+}
+
+/// A http client for a server built using this crate. It is usually not used directly, but
+/// passed to the resource clients generated by `#[derive(ResourceClient)]`. Cloning the client
+/// is cheap, all clones share the same connection pool.
+///
+/// The client only supports plain http, which is intended for services calling each other.
+#[derive(Clone, Debug)]
+pub struct Client {
+	http: gotham::hyper::Client<HttpConnector>,
+	base_url: String,
+	headers: HeaderMap
+}
+
+impl Client {
+	/// Create a new client for the server at `base_url`, e.g. `http://localhost:8080`. Only
+	/// `http` urls are supported.
+	pub fn new<U: Into<String>>(base_url: U) -> Self {
+		Self {
+			http: gotham::hyper::Client::new(),
+			base_url: base_url.into(),
+			headers: HeaderMap::new()
+		}
+	}
+
+	/// Send the header `name` with `value` with every request, e.g. for authentication.
+	pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+		self.headers.insert(name, value);
+		self
+	}
+
+	/// Send a request to the endpoint with `uri` of the resource registered at `path`. This
+	/// method is used by the code generated by `#[derive(ResourceClient)]`.
+	#[doc(hidden)]
+	pub async fn send<R, P, Q, B>(
+		&self,
+		path: &str,
+		method: Method,
+		uri: &str,
+		placeholders: Option<&P>,
+		params: Option<&Q>,
+		body: Option<B>
+	) -> Result<R::Output, ClientError>
+	where
+		R: ClientResponse,
+		P: Serialize + ?Sized,
+		Q: Serialize,
+		B: ClientRequestBody
+	{
+		let mut url = format!("{}/{path}", self.base_url.trim_end_matches('/'));
+		let uri = fill_placeholders(uri, placeholders)?;
+		if !uri.is_empty() {
+			url = format!("{}/{uri}", url.trim_end_matches('/'));
+		}
+		if let Some(params) = params {
+			let query = query_string(params)?;
+			if !query.is_empty() {
+				url = format!("{url}?{query}");
+			}
+		}
+
+		let mut req = Request::builder().method(method).uri(url);
+		for (name, value) in &self.headers {
+			req = req.header(name, value);
+		}
+		let body = match body {
+			Some(body) => {
+				let (mime, body) = body.into_request_body()?;
+				req = req.header(CONTENT_TYPE, mime.as_ref());
+				body
+			},
+			None => Body::empty()
+		};
+
+		let res = self.http.request(req.body(body)?).await?;
+		let (parts, body) = res.into_parts();
+		let body = to_bytes(body).await?;
+		R::from_response(http::Response::from_parts(parts, body))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::private::IdPlaceholder;
+
+	#[derive(Serialize)]
+	struct Params {
+		name: &'static str,
+		ids: Vec<u32>,
+		page: Option<u32>
+	}
+
+	#[test]
+	fn placeholders() {
+		let id = IdPlaceholder { id: "a/b c" };
+		assert_eq!(fill_placeholders(":id", Some(&id)).unwrap(), "a%2Fb%20c");
+		assert_eq!(
+			fill_placeholders("foo/:id:[a-z]+/bar", Some(&IdPlaceholder { id: 42 })).unwrap(),
+			"foo/42/bar"
+		);
+		assert_eq!(fill_placeholders("", None::<&()>).unwrap(), "");
+		assert!(fill_placeholders(":other", Some(&id)).is_err());
+	}
+
+	#[test]
+	fn query() {
+		let params = Params {
+			name: "Jane Doe",
+			ids: vec![1, 2],
+			page: None
+		};
+		assert_eq!(
+			query_string(&params).unwrap(),
+			"ids=1&ids=2&name=Jane%20Doe"
+		);
+	}
+}
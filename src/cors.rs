@@ -1,12 +1,14 @@
+use futures_util::future::{self, FutureExt};
 use gotham::{
-	handler::HandlerFuture,
+	anyhow,
+	handler::{Handler, HandlerFuture, NewHandler},
 	helpers::http::response::create_empty_response,
 	hyper::{
 		header::{
 			HeaderMap, HeaderName, HeaderValue, ACCESS_CONTROL_ALLOW_CREDENTIALS,
 			ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
-			ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS,
-			ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY
+			ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ACCESS_CONTROL_MAX_AGE,
+			ACCESS_CONTROL_REQUEST_HEADERS, ACCESS_CONTROL_REQUEST_METHOD, ORIGIN, VARY
 		},
 		Body, Method, Response, StatusCode
 	},
@@ -16,12 +18,21 @@ use gotham::{
 	router::{builder::ExtendRouteMatcher, route::matcher::AccessControlRequestMethodMatcher},
 	state::State
 };
-use std::{panic::RefUnwindSafe, pin::Pin};
+use std::{
+	fmt::{self, Debug},
+	panic::RefUnwindSafe,
+	pin::Pin,
+	sync::{Arc, RwLock}
+};
+
+/// The headers used for Private Network Access. Not (yet) part of hyper's list of known headers.
+const ACCESS_CONTROL_REQUEST_PRIVATE_NETWORK: &str = "access-control-request-private-network";
+const ACCESS_CONTROL_ALLOW_PRIVATE_NETWORK: &str = "access-control-allow-private-network";
 
 /// Specify the allowed origins of the request. It is up to the browser to check the validity of the
 /// origin. This, when sent to the browser, will indicate whether or not the request's origin was
 /// allowed to make the request.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Default)]
 pub enum Origin {
 	/// Do not send any `Access-Control-Allow-Origin` headers.
 	#[default]
@@ -30,8 +41,45 @@ pub enum Origin {
 	Star,
 	/// Set the `Access-Control-Allow-Origin` header to a single origin.
 	Single(String),
-	/// Copy the `Origin` header into the `Access-Control-Allow-Origin` header.
-	Copy
+	/// Copy the `Origin` header into the `Access-Control-Allow-Origin` header. This allows every
+	/// origin, which is dangerous when combined with [credentials](CorsConfig::credentials). Use
+	/// [Origin::List] instead whenever possible.
+	Copy,
+	/// Copy the `Origin` header into the `Access-Control-Allow-Origin` header if it is contained
+	/// in the list. Entries may contain a single `*` to match any subdomain, e.g.
+	/// `https://*.example.com`. Requests from other origins don't receive any CORS headers.
+	List(Vec<String>),
+	/// Copy the `Origin` header into the `Access-Control-Allow-Origin` header if the predicate
+	/// returns `true`. Requests from other origins don't receive any CORS headers.
+	Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync + RefUnwindSafe>)
+}
+
+impl Debug for Origin {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::None => f.write_str("None"),
+			Self::Star => f.write_str("Star"),
+			Self::Single(origin) => f.debug_tuple("Single").field(origin).finish(),
+			Self::Copy => f.write_str("Copy"),
+			Self::List(list) => f.debug_tuple("List").field(list).finish(),
+			Self::Predicate(_) => f.write_str("Predicate(..)")
+		}
+	}
+}
+
+/// Check if `origin` matches `pattern`, which may contain a single `*` matching any subdomain.
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+	match pattern.split_once('*') {
+		None => pattern == origin,
+		Some((prefix, suffix)) => {
+			origin.len() > prefix.len() + suffix.len()
+				&& origin.starts_with(prefix)
+				&& origin.ends_with(suffix)
+				&& origin[prefix.len()..origin.len() - suffix.len()]
+					.chars()
+					.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+		},
+	}
 }
 
 impl Origin {
@@ -41,16 +89,34 @@ impl Origin {
 			Self::None => None,
 			Self::Star => Some("*".parse().unwrap()),
 			Self::Single(origin) => Some(origin.parse().unwrap()),
-			Self::Copy => {
+			Self::Copy | Self::List(_) | Self::Predicate(_) => {
 				let headers = HeaderMap::borrow_from(state);
-				headers.get(ORIGIN).cloned()
+				headers
+					.get(ORIGIN)
+					.filter(|_| !self.rejects(state))
+					.cloned()
 			}
 		}
 	}
 
 	/// Returns true if the `Vary` header has to include `Origin`.
 	fn varies(&self) -> bool {
-		matches!(self, Self::Copy)
+		matches!(self, Self::Copy | Self::List(_) | Self::Predicate(_))
+	}
+
+	/// Returns true if the request's origin is not allowed and must not receive any CORS headers.
+	fn rejects(&self, state: &State) -> bool {
+		let origin = HeaderMap::borrow_from(state)
+			.get(ORIGIN)
+			.and_then(|origin| origin.to_str().ok());
+		match (self, origin) {
+			(Self::List(list), Some(origin)) => {
+				!list.iter().any(|pattern| origin_matches(pattern, origin))
+			},
+			(Self::Predicate(predicate), Some(origin)) => !predicate(origin),
+			(Self::List(_) | Self::Predicate(_), None) => true,
+			_ => false
+		}
 	}
 }
 
@@ -148,6 +214,9 @@ impl Headers {
 /// 	})
 /// );
 /// ```
+///
+/// Alternatively, a [Resource](crate::Resource) can specify its own configuration using
+/// `#[resource(cors = "function_name")]`, which overrides the one in the [State].
 #[derive(Clone, Debug, Default, NewMiddleware, StateData)]
 pub struct CorsConfig {
 	/// The allowed origins.
@@ -157,7 +226,16 @@ pub struct CorsConfig {
 	/// The amount of seconds that the preflight request can be cached.
 	pub max_age: u64,
 	/// Whether or not the request may be made with supplying credentials.
-	pub credentials: bool
+	pub credentials: bool,
+	/// The response headers that may be read by the browser, in addition to the CORS-safelisted
+	/// ones.
+	pub expose_headers: Vec<HeaderName>,
+	/// The allowed methods. If `None`, all methods registered for the requested path are allowed.
+	pub methods: Option<Vec<Method>>,
+	/// Whether or not requests from public websites to this server in a private network are
+	/// allowed, as requested by Chrome's
+	/// [Private Network Access](https://wicg.github.io/private-network-access/).
+	pub allow_private_network: bool
 }
 
 impl Middleware for CorsConfig {
@@ -190,19 +268,36 @@ pub fn handle_cors(state: &State, res: &mut Response<Body>) {
 
 		// if the origin is copied over, we should tell the browser by specifying the Vary header
 		if cfg.origin.varies() {
-			let vary = headers
-				.get(VARY)
-				.map(|vary| format!("{},origin", vary.to_str().unwrap()));
-			headers.insert(VARY, vary.as_deref().unwrap_or("origin").parse().unwrap());
+			let vary = match headers.get(VARY) {
+				Some(vary) => {
+					let mut vary = vary.as_bytes().to_vec();
+					vary.extend_from_slice(b",origin");
+					// appending ascii to a valid header value always yields a valid header value
+					HeaderValue::from_bytes(&vary).unwrap()
+				},
+				None => HeaderValue::from_static("origin")
+			};
+			headers.insert(VARY, vary);
 		}
 
 		// if we allow credentials, tell the browser
-		if cfg.credentials {
+		if cfg.credentials && !cfg.origin.rejects(state) {
 			headers.insert(
 				ACCESS_CONTROL_ALLOW_CREDENTIALS,
 				HeaderValue::from_static("true")
 			);
 		}
+
+		// tell the browser which headers it may expose, preflight responses don't need them
+		if !cfg.expose_headers.is_empty()
+			&& !cfg.origin.rejects(state)
+			&& Method::borrow_from(state) != Method::OPTIONS
+		{
+			headers.insert(
+				ACCESS_CONTROL_EXPOSE_HEADERS,
+				cfg.expose_headers.join(",").parse().unwrap()
+			);
+		}
 	}
 }
 
@@ -234,23 +329,137 @@ where
 	fn cors(&mut self, path: &str, method: Method);
 }
 
-pub(crate) fn cors_preflight_handler(state: State) -> (State, Response<Body>) {
+/// The handler for preflight requests. It knows all methods that were registered for its path.
+#[derive(Clone, Debug)]
+pub(crate) struct CorsPreflightHandler {
+	methods: Arc<RwLock<Vec<Method>>>,
+	/// The CORS configuration of the resource, overriding the one in the state.
+	cors: Option<CorsConfig>
+}
+
+impl CorsPreflightHandler {
+	pub(crate) fn new(methods: Arc<RwLock<Vec<Method>>>, cors: Option<CorsConfig>) -> Self {
+		Self { methods, cors }
+	}
+}
+
+impl NewHandler for CorsPreflightHandler {
+	type Instance = Self;
+
+	fn new_handler(&self) -> anyhow::Result<Self> {
+		Ok(self.clone())
+	}
+}
+
+impl Handler for CorsPreflightHandler {
+	fn handle(self, mut state: State) -> Pin<Box<HandlerFuture>> {
+		if let Some(cors) = self.cors {
+			state.put(cors);
+		}
+		let methods = self.methods.read().unwrap();
+		let res = cors_preflight_handler(state, &methods);
+		future::ok(res).boxed()
+	}
+}
+
+/// Check that the preflight request only asks for what is allowed by the config. Returns the
+/// status code of the response otherwise.
+fn validate_preflight(
+	state: &State,
+	config: Option<&CorsConfig>,
+	methods: &[Method]
+) -> Result<(), StatusCode> {
+	let headers = HeaderMap::borrow_from(state);
+
+	let method = headers
+		.get(ACCESS_CONTROL_REQUEST_METHOD)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.to_ascii_uppercase().parse::<Method>().ok())
+		.ok_or(StatusCode::BAD_REQUEST)?;
+
+	// rejected origins don't receive any CORS headers, so there is nothing to validate
+	if config.is_some_and(|cfg| cfg.origin.rejects(state)) {
+		return Ok(());
+	}
+
+	let allowed_methods = config
+		.and_then(|cfg| cfg.methods.as_deref())
+		.unwrap_or(methods);
+	if !allowed_methods.contains(&method) {
+		return Err(StatusCode::FORBIDDEN);
+	}
+
+	if requests_private_network(state) && !config.is_some_and(|cfg| cfg.allow_private_network) {
+		return Err(StatusCode::FORBIDDEN);
+	}
+
+	// without a list of allowed headers, we don't send Access-Control-Allow-Headers and leave
+	// the decision to the browser
+	if let Some(Headers::List(allowed_headers)) = config.map(|cfg| &cfg.headers) {
+		let request_headers = headers
+			.get_all(ACCESS_CONTROL_REQUEST_HEADERS)
+			.iter()
+			.map(|value| value.to_str().map_err(|_| StatusCode::BAD_REQUEST))
+			.collect::<Result<Vec<_>, _>>()?;
+		let allowed = |name: &str| {
+			allowed_headers.is_empty()
+				|| allowed_headers
+					.iter()
+					.any(|allowed| allowed.as_str().eq_ignore_ascii_case(name))
+		};
+		if !request_headers
+			.into_iter()
+			.flat_map(|value| value.split(','))
+			.map(str::trim)
+			.filter(|name| !name.is_empty())
+			.all(allowed)
+		{
+			return Err(StatusCode::FORBIDDEN);
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns true if this is a Private Network Access preflight request.
+fn requests_private_network(state: &State) -> bool {
+	HeaderMap::borrow_from(state)
+		.get(ACCESS_CONTROL_REQUEST_PRIVATE_NETWORK)
+		.is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"true"))
+}
+
+fn cors_preflight_handler(state: State, methods: &[Method]) -> (State, Response<Body>) {
 	let config = CorsConfig::try_borrow_from(&state);
 
+	// reject invalid preflight requests
+	if let Err(status) = validate_preflight(&state, config, methods) {
+		debug!("Rejecting invalid preflight request with status {status}");
+		let res = create_empty_response(&state, status);
+		return (state, res);
+	}
+
 	// prepare the response
 	let mut res = create_empty_response(&state, StatusCode::NO_CONTENT);
 	let headers = res.headers_mut();
 	let mut vary: Vec<HeaderName> = Vec::new();
 
-	// copy the request method over to the response
-	let method = HeaderMap::borrow_from(&state)
-		.get(ACCESS_CONTROL_REQUEST_METHOD)
-		.unwrap()
-		.clone();
-	headers.insert(ACCESS_CONTROL_ALLOW_METHODS, method);
 	vary.push(ACCESS_CONTROL_REQUEST_METHOD);
+	let rejected = config.is_some_and(|cfg| cfg.origin.rejects(&state));
 
-	if let Some(cfg) = config {
+	// tell the browser which methods are allowed
+	if !rejected {
+		let methods = config
+			.and_then(|cfg| cfg.methods.as_deref())
+			.unwrap_or(methods);
+		let methods = methods
+			.iter()
+			.map(Method::as_str)
+			.collect::<Vec<_>>()
+			.join(",");
+		headers.insert(ACCESS_CONTROL_ALLOW_METHODS, methods.parse().unwrap());
+	}
+
+	if let Some(cfg) = config.filter(|_| !rejected) {
 		// if we allow any headers, copy them over
 		if let Some(header) = cfg.headers.header_value(&state) {
 			headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, header);
@@ -265,6 +474,14 @@ pub(crate) fn cors_preflight_handler(state: State) -> (State, Response<Body>) {
 		if let Some(age) = config.map(|cfg| cfg.max_age) {
 			headers.insert(ACCESS_CONTROL_MAX_AGE, age.into());
 		}
+
+		// allow requests from public to private networks
+		if cfg.allow_private_network && requests_private_network(&state) {
+			headers.insert(
+				ACCESS_CONTROL_ALLOW_PRIVATE_NETWORK,
+				HeaderValue::from_static("true")
+			);
+		}
 	}
 
 	// make sure the browser knows that this request was based on the method
@@ -281,9 +498,61 @@ where
 	P: RefUnwindSafe + Send + Sync + 'static
 {
 	fn cors(&mut self, path: &str, method: Method) {
-		let matcher = AccessControlRequestMethodMatcher::new(method);
+		let matcher = AccessControlRequestMethodMatcher::new(method.clone());
+		let methods = Arc::new(RwLock::new(vec![method]));
 		self.options(path)
 			.extend_route_matcher(matcher)
-			.to(cors_preflight_handler);
+			.to_new_handler(CorsPreflightHandler::new(methods, None));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn origin_patterns() {
+		assert!(origin_matches("https://example.com", "https://example.com"));
+		assert!(!origin_matches("https://example.com", "http://example.com"));
+		assert!(origin_matches(
+			"https://*.example.com",
+			"https://foo.example.com"
+		));
+		assert!(origin_matches(
+			"https://*.example.com",
+			"https://a.b.example.com"
+		));
+		assert!(!origin_matches(
+			"https://*.example.com",
+			"https://example.com"
+		));
+		assert!(!origin_matches(
+			"https://*.example.com",
+			"https://evil.com/.example.com"
+		));
+		assert!(!origin_matches(
+			"https://*.example.com",
+			"https://fooexample.com"
+		));
+	}
+
+	#[test]
+	fn non_ascii_vary() {
+		State::with_new(|state| {
+			let mut headers = HeaderMap::new();
+			headers.insert(ORIGIN, HeaderValue::from_static("https://example.org"));
+			state.put(headers);
+			state.put(Method::GET);
+			state.put(CorsConfig {
+				origin: Origin::Copy,
+				..Default::default()
+			});
+
+			let mut res = Response::new(Body::empty());
+			res.headers_mut()
+				.insert(VARY, HeaderValue::from_bytes(b"caf\xe9").unwrap());
+			handle_cors(state, &mut res);
+			assert_eq!(res.headers()[VARY].as_bytes(), b"caf\xe9,origin");
+		});
 	}
 }
@@ -0,0 +1,292 @@
+use crate::response::ResourceError;
+use gotham::{
+	cookie::CookieJar,
+	handler::HandlerFuture,
+	helpers::http::response::create_response,
+	hyper::{
+		header::{HeaderMap, HeaderName, HOST, ORIGIN},
+		Body, Method, Response, StatusCode
+	},
+	middleware::{cookie::CookieParser, Middleware},
+	mime::APPLICATION_JSON,
+	prelude::*,
+	state::State
+};
+use std::pin::Pin;
+
+/// The header used by browsers to tell the server whether a request was made from the same origin.
+/// Not (yet) part of hyper's list of known headers.
+const SEC_FETCH_SITE: &str = "sec-fetch-site";
+
+/// This is the configuration for the CSRF protection of all endpoints with a non-safe HTTP method,
+/// i.e. everything except `GET`, `HEAD`, `OPTIONS` and `TRACE`. Requests failing the check are
+/// rejected with a _403 Forbidden_ response. This is recommended whenever you use
+/// [AuthSource::Cookie](crate::AuthSource::Cookie).
+///
+/// Just like the [CorsConfig](crate::CorsConfig), you need to put this type into gotham's [State]:
+///
+/// ```rust,no_run
+/// # use gotham::{hyper::header::HeaderName, router::builder::*, pipeline::*, state::State};
+/// # use gotham_restful::*;
+/// # use serde::Deserialize;
+/// # #[derive(Clone, Deserialize)]
+/// # struct AuthData { exp: u64 }
+/// # #[cfg_attr(feature = "cargo-clippy", allow(clippy::needless_doctest_main))]
+/// fn main() {
+/// 	let auth: AuthMiddleware<AuthData, _> = AuthMiddleware::new(
+/// 		AuthSource::Cookie("session".to_owned()),
+/// 		AuthValidation::default(),
+/// 		StaticAuthHandler::from_array(b"zlBsA2QXnkmpe0QTh8uCvtAEa4j33YAc")
+/// 	);
+/// 	let csrf = CsrfConfig::DoubleSubmitCookie {
+/// 		cookie: "csrf".to_owned(),
+/// 		header: HeaderName::from_static("x-csrf-token")
+/// 	};
+/// 	let (chain, pipelines) = single_pipeline(new_pipeline().add(auth).add(csrf).build());
+/// 	gotham::start(
+/// 		"127.0.0.1:8080",
+/// 		build_router(chain, pipelines, |route| {
+/// 			// your routing logic
+/// 		})
+/// 	);
+/// }
+/// ```
+#[derive(Clone, Debug, NewMiddleware, StateData)]
+pub enum CsrfConfig {
+	/// Require the value of the cookie `cookie` to be repeated in the header `header`. Your
+	/// application is responsible for setting that cookie to a random value, e.g. on login.
+	DoubleSubmitCookie { cookie: String, header: HeaderName },
+
+	/// Require the request to originate from the same origin as the server, using the
+	/// `Sec-Fetch-Site` header if present and the `Origin` header otherwise. Requests without
+	/// either of those headers were not made by a browser and are always allowed. Requests from
+	/// any of the `trusted_origins` (e.g. `https://example.org`) are allowed as well.
+	///
+	/// When comparing the `Origin` header with the `Host` header, the server is assumed to be
+	/// reached over https. If your server is reached over plain http, add its origin (e.g.
+	/// `http://localhost:8080`) to the `trusted_origins`.
+	SameOrigin { trusted_origins: Vec<String> }
+}
+
+impl Middleware for CsrfConfig {
+	fn call<Chain>(self, mut state: State, chain: Chain) -> Pin<Box<HandlerFuture>>
+	where
+		Chain: FnOnce(State) -> Pin<Box<HandlerFuture>>
+	{
+		state.put(self);
+		chain(state)
+	}
+}
+
+/// Check whether `origin` is the origin of a server that is reached over https at `host`.
+fn is_same_origin(origin: &str, host: &str) -> bool {
+	fn without_default_port(host: &str) -> &str {
+		host.strip_suffix(":443").unwrap_or(host)
+	}
+
+	origin.strip_prefix("https://").is_some_and(|origin| {
+		without_default_port(origin).eq_ignore_ascii_case(without_default_port(host))
+	})
+}
+
+/// Compare two byte slices in constant time (with regards to their content).
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+impl CsrfConfig {
+	/// Returns `Err` with a message if the request does not pass this CSRF check.
+	fn check(&self, state: &State) -> Result<(), &'static str> {
+		let headers = HeaderMap::borrow_from(state);
+		match self {
+			Self::DoubleSubmitCookie { cookie, header } => {
+				let cookie = CookieJar::try_borrow_from(state)
+					.map(|jar| jar.get(cookie).map(|cookie| cookie.value().to_owned()))
+					.unwrap_or_else(|| {
+						CookieParser::from_state(state)
+							.get(cookie)
+							.map(|cookie| cookie.value().to_owned())
+					})
+					.filter(|cookie| !cookie.is_empty())
+					.ok_or("Missing CSRF cookie")?;
+				let header = headers.get(header).ok_or("Missing CSRF header")?;
+				match constant_time_eq(cookie.as_bytes(), header.as_bytes()) {
+					true => Ok(()),
+					false => Err("Invalid CSRF token")
+				}
+			},
+
+			Self::SameOrigin { trusted_origins } => {
+				let origin = headers.get(ORIGIN).and_then(|origin| origin.to_str().ok());
+				let trusted =
+					|| origin.is_some_and(|origin| trusted_origins.iter().any(|o| o == origin));
+
+				if let Some(site) = headers.get(SEC_FETCH_SITE) {
+					return match site.as_bytes() {
+						b"same-origin" | b"none" => Ok(()),
+						_ if trusted() => Ok(()),
+						_ => Err("Cross-origin request rejected")
+					};
+				}
+
+				let origin = match origin {
+					Some(origin) => origin,
+					None => return Ok(())
+				};
+				let host = headers.get(HOST).and_then(|host| host.to_str().ok());
+				if host.is_some_and(|host| is_same_origin(origin, host)) || trusted() {
+					Ok(())
+				} else {
+					Err("Cross-origin request rejected")
+				}
+			}
+		}
+	}
+}
+
+/// Check the request against the [CsrfConfig] in the `state`, if any. Returns a _403 Forbidden_
+/// response if the check failed.
+pub(crate) fn handle_csrf(state: &State) -> Option<Response<Body>> {
+	let method = Method::borrow_from(state);
+	if matches!(
+		*method,
+		Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+	) {
+		return None;
+	}
+
+	let cfg = CsrfConfig::try_borrow_from(state)?;
+	let err = cfg.check(state).err()?;
+	debug!("CSRF check failed: {err}");
+	let error: ResourceError = err.into();
+	// ResourceError serialization cannot fail
+	let json = serde_json::to_string(&error).unwrap();
+	Some(create_response(
+		state,
+		StatusCode::FORBIDDEN,
+		APPLICATION_JSON,
+		json
+	))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use gotham::{cookie::Cookie, hyper::header::HeaderValue};
+
+	fn double_submit() -> CsrfConfig {
+		CsrfConfig::DoubleSubmitCookie {
+			cookie: "csrf".to_owned(),
+			header: HeaderName::from_static("x-csrf-token")
+		}
+	}
+
+	fn same_origin() -> CsrfConfig {
+		CsrfConfig::SameOrigin {
+			trusted_origins: vec!["https://trusted.org".to_owned()]
+		}
+	}
+
+	fn check(cfg: &CsrfConfig, headers: &[(&str, &str)], cookie: Option<&str>) -> bool {
+		let mut ok = false;
+		State::with_new(|state| {
+			let mut map = HeaderMap::new();
+			for (name, value) in headers {
+				map.insert(
+					HeaderName::from_bytes(name.as_bytes()).unwrap(),
+					HeaderValue::from_str(value).unwrap()
+				);
+			}
+			state.put(map);
+			let mut jar = CookieJar::new();
+			if let Some(cookie) = cookie {
+				jar.add_original(Cookie::new("csrf", cookie.to_owned()));
+			}
+			state.put(jar);
+			ok = cfg.check(state).is_ok();
+		});
+		ok
+	}
+
+	#[test]
+	fn double_submit_cookie() {
+		let cfg = double_submit();
+		assert!(check(&cfg, &[("x-csrf-token", "abc")], Some("abc")));
+		assert!(!check(&cfg, &[("x-csrf-token", "abd")], Some("abc")));
+		assert!(!check(&cfg, &[("x-csrf-token", "abc")], None));
+		assert!(!check(&cfg, &[], Some("abc")));
+		assert!(!check(&cfg, &[("x-csrf-token", "")], Some("")));
+	}
+
+	#[test]
+	fn same_origin_sec_fetch_site() {
+		let cfg = same_origin();
+		assert!(check(&cfg, &[(SEC_FETCH_SITE, "same-origin")], None));
+		assert!(check(&cfg, &[(SEC_FETCH_SITE, "none")], None));
+		assert!(!check(&cfg, &[(SEC_FETCH_SITE, "same-site")], None));
+		assert!(!check(&cfg, &[(SEC_FETCH_SITE, "cross-site")], None));
+		assert!(check(
+			&cfg,
+			&[
+				(SEC_FETCH_SITE, "cross-site"),
+				("origin", "https://trusted.org")
+			],
+			None
+		));
+	}
+
+	#[test]
+	fn same_origin_origin_header() {
+		let cfg = same_origin();
+		assert!(check(&cfg, &[], None));
+		assert!(check(
+			&cfg,
+			&[("origin", "https://example.org"), ("host", "example.org")],
+			None
+		));
+		assert!(!check(
+			&cfg,
+			&[("origin", "https://evil.org"), ("host", "example.org")],
+			None
+		));
+		assert!(check(
+			&cfg,
+			&[("origin", "https://trusted.org"), ("host", "example.org")],
+			None
+		));
+	}
+
+	#[test]
+	fn same_origin_scheme_and_port() {
+		let cfg = same_origin();
+		assert!(!check(
+			&cfg,
+			&[("origin", "http://example.org"), ("host", "example.org")],
+			None
+		));
+		assert!(check(
+			&cfg,
+			&[
+				("origin", "https://example.org:443"),
+				("host", "Example.org")
+			],
+			None
+		));
+		assert!(check(
+			&cfg,
+			&[
+				("origin", "https://example.org:8443"),
+				("host", "example.org:8443")
+			],
+			None
+		));
+		assert!(!check(
+			&cfg,
+			&[
+				("origin", "https://example.org:8443"),
+				("host", "example.org")
+			],
+			None
+		));
+	}
+}
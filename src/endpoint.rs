@@ -1,7 +1,12 @@
 #[cfg(feature = "openapi")]
-use crate::openapi::operation::OperationId;
+use crate::{
+	openapi::{fields::OpenapiField, operation::OperationId},
+	ResponseHeader
+};
 use crate::{IntoResponse, RequestBody};
 use futures_util::future::BoxFuture;
+#[cfg(feature = "openapi")]
+use gotham::hyper::StatusCode;
 use gotham::{
 	extractor::{PathExtractor, QueryStringExtractor},
 	hyper::{Body, Method, Response},
@@ -102,6 +107,56 @@ pub trait Endpoint {
 		None
 	}
 
+	/// Override the tags of the resource in the openapi specification. Usually taken from the
+	/// `tag` attribute when using the proc macro.
+	#[openapi_only]
+	fn tags() -> Vec<Cow<'static, str>> {
+		Vec::new()
+	}
+
+	/// Mark this endpoint as deprecated in the openapi specification. Usually taken from the
+	/// `#[deprecated]` attribute when using the proc macro.
+	#[openapi_only]
+	fn deprecated() -> bool {
+		false
+	}
+
+	/// Document headers of the response for the given status code in addition to those of the
+	/// [Output](Self::Output) type, e.g. headers set using [Success::header](crate::Success::header).
+	/// Usually taken from the `headers` attribute when using the proc macro.
+	#[openapi_only]
+	fn headers(_code: StatusCode) -> Vec<ResponseHeader> {
+		Vec::new()
+	}
+
+	/// Leave this endpoint out of the openapi specification, while still routing requests to it.
+	/// Usually taken from the `hidden` attribute when using the proc macro.
+	#[openapi_only]
+	fn hidden() -> bool {
+		false
+	}
+
+	/// The examples and deprecation of the fields of the [Placeholders](Self::Placeholders). Usually
+	/// taken from their [OpenapiFields](crate::OpenapiFields) implementation when using the proc macro.
+	#[openapi_only]
+	fn placeholders_fields() -> Vec<OpenapiField> {
+		Vec::new()
+	}
+
+	/// The examples and deprecation of the fields of the [Params](Self::Params). Usually taken
+	/// from their [OpenapiFields](crate::OpenapiFields) implementation when using the proc macro.
+	#[openapi_only]
+	fn params_fields() -> Vec<OpenapiField> {
+		Vec::new()
+	}
+
+	/// The examples and deprecation of the fields of the [Body](Self::Body). Usually taken from
+	/// its [OpenapiFields](crate::OpenapiFields) implementation when using the proc macro.
+	#[openapi_only]
+	fn body_fields() -> Vec<OpenapiField> {
+		Vec::new()
+	}
+
 	/// The handler for this endpoint.
 	fn handle(
 		state: &mut State,
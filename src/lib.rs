@@ -200,11 +200,17 @@
 //! To make life easier for common use-cases, this create offers a few features that might be helpful
 //! when you implement your web server.  The complete feature list is
 //!  - [`auth`](#authentication-feature) Advanced JWT middleware
+//!  - [`client`](#client-feature) typed clients for your resources
 //!  - [`cors`](#cors-feature) CORS handling for all endpoint handlers
 //!  - [`database`](#database-feature) diesel middleware support
 //!  - `errorlog` log errors returned from endpoint handlers
-//!  - `full` enables all features except `without-openapi`
+//!  - `full` enables all features except `without-openapi` and `test-util`
 //!  - [`openapi`](#openapi-feature) router additions to generate an openapi spec
+//!  - `openapi-yaml` serve the openapi spec in YAML format, see `GetOpenapi::openapi_spec_yaml`
+//!  - `swagger-ui` serve the openapi documentation using Swagger UI, see `GetOpenapi::openapi_swagger_ui`
+//!  - `test-util` authentication without tokens for your tests, see [`MockAuthMiddleware`]
+//!  - `validate-responses` check responses against their openapi schema while debugging, see
+//!    `OpenapiRouter::validate_responses`
 //!  - `without-openapi` (**default**) disables `openapi` support.
 //!
 //! ## Authentication Feature
@@ -265,6 +271,57 @@
 //! # }
 //! ```
 //!
+//! When the request cannot be authenticated, the [`AuthError`] returned by `auth.ok()` responds
+//! with a json body containing a machine-readable code of the [`AuthFailure`], e.g.
+//! `token_expired` or `missing_token`, so that clients can tell whether to refresh their token or
+//! to log in again.
+//!
+//! If the token is taken from a cookie, all endpoints that change state are prone to cross-site
+//! request forgery. Add a [`CsrfConfig`] to your pipeline to protect them.
+//!
+//! ## Client Feature
+//!
+//! If your services call each other, you can let this crate generate a typed client for your
+//! resources instead of writing one by hand. Enable the `client` feature and derive
+//! `ResourceClient` next to `Resource`. This generates a `FooResourceClient` with one async
+//! method per endpoint that takes the same arguments as the endpoint handler (except the state,
+//! authentication and database connection) and returns the output of the endpoint. The client and
+//! its methods have the same visibility as the resource struct:
+//!
+//! ```rust,no_run
+//! # #[macro_use] extern crate gotham_restful_derive;
+//! # #[cfg(feature = "client")]
+//! # mod client_feature_enabled {
+//! # use gotham_restful::{client::*, *};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Resource, ResourceClient)]
+//! #[resource(read)]
+//! struct FooResource;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! # #[cfg_attr(feature = "openapi", derive(openapi_type::OpenapiType))]
+//! struct Foo {
+//! 	id: u64
+//! }
+//!
+//! #[read]
+//! fn read(id: u64) -> Success<Foo> {
+//! 	Foo { id }.into()
+//! }
+//!
+//! async fn call_foo() -> Result<Foo, ClientError> {
+//! 	let client = Client::new("http://localhost:8080");
+//! 	FooResourceClient::new(client, "foo").read(42).await
+//! }
+//! # }
+//! ```
+//!
+//! The types of the placeholders, parameters and request bodies need to implement `Serialize`,
+//! and successful responses are deserialized into the type returned by the endpoint. Responses
+//! with an error status code are returned as `ClientError::Status`. The client only supports plain
+//! http, so it is meant for services calling each other within a trusted network, not for `https`
+//! urls.
+//!
 //! ## CORS Feature
 //!
 //! The cors feature allows an easy usage of this web server from other origins. By default, only
@@ -295,7 +352,8 @@
 //! 		origin: Origin::Copy,
 //! 		headers: Headers::List(vec![CONTENT_TYPE]),
 //! 		max_age: 0,
-//! 		credentials: true
+//! 		credentials: true,
+//! 		..Default::default()
 //! 	};
 //! 	let (chain, pipelines) = single_pipeline(new_pipeline().add(cors).build());
 //! 	gotham::start(
@@ -309,6 +367,34 @@
 //! # }
 //! ```
 //!
+//! A resource can also use its own configuration that overrides the one in the state. Simply
+//! add `cors = "function_name"` to the resource attribute, where the function returns the
+//! [`CorsConfig`] for that resource:
+//!
+//! ```rust
+//! # #[macro_use] extern crate gotham_restful_derive;
+//! # #[cfg(feature = "cors")]
+//! # mod cors_feature_enabled {
+//! # use gotham_restful::{*, cors::*};
+//! #[derive(Resource)]
+//! #[resource(read_all, cors = "public_cors")]
+//! struct PublicResource;
+//!
+//! fn public_cors() -> CorsConfig {
+//! 	CorsConfig {
+//! 		origin: Origin::Star,
+//! 		..Default::default()
+//! 	}
+//! }
+//! # #[read_all]
+//! # fn read_all() {}
+//! # }
+//! ```
+//!
+//! Preflight requests are answered for all endpoints, including `GET` endpoints, which are only
+//! preflighted by browsers when using non-simple headers. They are validated against the
+//! configuration of the resource, or the one in the pipeline if the resource has none.
+//!
 //! The cors feature can also be used for non-resource handlers. Take a look at [`CorsRoute`]
 //! for an example.
 //!
@@ -433,7 +519,120 @@
 //! The first one will return the generated openapi specification in JSON format, allowing you to easily
 //! generate clients in different languages without worying to exactly replicate your api in each of those
 //! languages. The second one will return documentation in HTML format, so you can easily view your
-//! api and share it with other people.
+//! api and share it with other people. If you need the specification in YAML format, enable the
+//! `openapi-yaml` feature and register it using `route.openapi_spec_yaml("openapi.yaml")`.
+//!
+//! The documentation is rendered using Redoc, which is loaded from its CDN with subresource integrity.
+//! If you prefer Swagger UI, which allows you to try out your endpoints, enable the `swagger-ui`
+//! feature and register it using `route.openapi_swagger_ui("swagger")`. All of its assets are
+//! embedded, no CDN is involved, so it also works without internet access.
+//!
+//! If you want to export the specification without starting a server, for example to commit it to
+//! your repository or to generate clients in CI, use [`OpenapiSpec`]. Committed specifications can
+//! be compared using [`openapi::diff`], or [`openapi::assert_no_breaking_changes`] in a test.
+//!
+//! Besides the title and version from [`OpenapiInfo`], the OpenAPI router can add a description,
+//! contact and license information, and servers with templated urls like
+//! `https://{region}.example.org/api`, which are described using [`OpenapiServer`], to the
+//! specification.
+//!
+//! Operations are grouped by their resource: every endpoint is tagged with the name of the resource
+//! struct without its `Resource` suffix, and the doc comment of the struct is used as the description
+//! of that tag. Use `#[resource(tag = "...")]` to choose a different name, or `#[endpoint(tag = "...")]`
+//! to move a single endpoint into another group. The first paragraph of an endpoint's doc comment
+//! becomes the summary of the operation, and the remaining paragraphs its description. Endpoints
+//! annotated with `#[deprecated]` are marked as deprecated, and doc comments on the fields of path
+//! and query parameter structs become the descriptions of those parameters. Fields of request and
+//! response bodies keep their doc comments in the schema of the body.
+//!
+//! The `OpenapiType` derive does not know about examples or `#[deprecated]` fields. Add the
+//! [`#[openapi_fields]`](crate::openapi_fields) attribute above the derive to use
+//! `#[openapi(example = ...)]` and `#[deprecated]` on the fields of parameter and request body types:
+//!
+//! ```rust
+//! # #[macro_use] extern crate gotham_restful_derive;
+//! # #[cfg(feature = "openapi")]
+//! # mod openapi_feature_enabled {
+//! # #![allow(deprecated)]
+//! # use gotham::prelude::*;
+//! # use gotham_restful::*;
+//! # use openapi_type::OpenapiType;
+//! # use serde::Deserialize;
+//! # #[derive(Resource)]
+//! # #[resource(search)]
+//! # struct FooResource;
+//! #[openapi_fields]
+//! #[derive(Clone, Deserialize, OpenapiType, StateData, StaticResponseExtender)]
+//! struct FooSearch {
+//! 	/// Only return foos whose name contains this string.
+//! 	#[openapi(example = "bar")]
+//! 	name: String,
+//! 	#[deprecated]
+//! 	limit: Option<u16>
+//! }
+//!
+//! #[search]
+//! fn search(query: FooSearch) -> Success<Vec<u64>> {
+//! # drop(query);
+//! 	Vec::new().into()
+//! }
+//! # }
+//! ```
+//!
+//! Headers set using [`Success::header`] or [`NoContent::header`] are not part of the response type,
+//! so they can't be documented automatically. Use `#[endpoint(headers = "...")]` with the name of a
+//! function that takes the status code and returns the [`ResponseHeader`]s of that response:
+//!
+//! ```rust
+//! # #[macro_use] extern crate gotham_restful_derive;
+//! # #[cfg(feature = "openapi")]
+//! # mod openapi_feature_enabled {
+//! # use gotham::hyper::{header::ETAG, StatusCode};
+//! # use gotham_restful::*;
+//! # #[derive(Resource)]
+//! # #[resource(read_all)]
+//! # struct FooResource;
+//! fn etag_header(_code: StatusCode) -> Vec<ResponseHeader> {
+//! 	vec![ResponseHeader::new(ETAG).with_description("The version of the foos.")]
+//! }
+//!
+//! #[read_all(headers = "etag_header")]
+//! fn read_all() -> NoContent {
+//! 	let mut res = NoContent::default();
+//! 	res.header(ETAG, "\"1\"".parse().unwrap());
+//! 	res
+//! }
+//! # }
+//! ```
+//!
+//! Operations get an id built from the name of the endpoint and the path it was registered with.
+//! Call `operation_id_style` on the OpenAPI router with [`OperationIdStyle::SnakeCase`] or
+//! [`OperationIdStyle::CamelCase`] to get ids like `read_all_foo` or `readAllFoo` that are valid
+//! identifiers and leave out placeholders. An id that is
+//! already in use, including one set using `#[endpoint(operation_id = "...")]`, gets a number
+//! appended and a warning is logged.
+//!
+//! One router can serve several OpenAPI documents, for example a public and an internal one. Call
+//! `add_document` on the OpenAPI router to create another document, and register resources inside
+//! `in_documents` to add them to the named documents instead of the one passed to `with_openapi`,
+//! which is called [`DEFAULT_OPENAPI_DOCUMENT`]. Endpoints annotated with `#[endpoint(hidden)]`
+//! are still routed, but left out of all documents.
+//!
+//! Call `validate_requests` on the OpenAPI router to check the path and query parameters and json
+//! request bodies of all endpoints registered afterwards against their schema. Requests that do not
+//! match are answered with _422 Unprocessable Entity_ and a list of all violations, before your
+//! endpoint is called. Only constraints that are part of the generated schema can be enforced, and
+//! `pattern`s are matched using the `regex` crate rather than as ECMA-262 regular expressions.
+//!
+//! To catch responses that drift away from the specification, enable the `validate-responses`
+//! feature and call `validate_responses` on the OpenAPI router. The status code and json body of
+//! every response are then checked against the [`ResponseSchema`] of the endpoint, and violations
+//! are logged or cause a panic, depending on the `ResponseValidationMode`. As this buffers all
+//! response bodies, it is meant for debug builds and tests.
+//!
+//! By default, the specification uses OpenAPI 3.0. If your tooling prefers OpenAPI 3.1, which uses
+//! JSON Schema 2020-12, call `openapi_version` on the OpenAPI router with [`OpenapiVersion::V3_1`]
+//! and the specification will be converted before it is being served. Webhooks are not supported.
 //!
 //! ### Gotchas
 //!
@@ -500,6 +699,8 @@ pub mod private {
 	use gotham::hyper::StatusCode;
 	#[cfg(feature = "auth")]
 	use gotham::state::{FromState, State};
+	#[cfg(feature = "openapi")]
+	use std::marker::PhantomData;
 
 	/// This method is used by the endpoint macro to generate a good error message
 	/// when the used AuthData type does not implement Clone.
@@ -547,6 +748,54 @@ pub mod private {
 			self()
 		}
 	}
+
+	/// This trait is used by the endpoint macro to generate a good error message
+	/// when the headers function has the wrong signature.
+	#[cfg(feature = "openapi")]
+	pub trait CustomHeaders {
+		fn headers(self, code: StatusCode) -> Vec<crate::ResponseHeader>;
+	}
+
+	#[cfg(feature = "openapi")]
+	impl<F> CustomHeaders for F
+	where
+		F: FnOnce(StatusCode) -> Vec<crate::ResponseHeader>
+	{
+		#[inline]
+		fn headers(self, code: StatusCode) -> Vec<crate::ResponseHeader> {
+			self(code)
+		}
+	}
+
+	/// This type is used by the endpoint macro to get the [OpenapiFields](crate::OpenapiFields)
+	/// of a type if it implements that trait, and no fields otherwise.
+	#[cfg(feature = "openapi")]
+	#[derive(Debug)]
+	pub struct FieldsOf<T>(pub PhantomData<T>);
+
+	#[cfg(feature = "openapi")]
+	pub trait WithOpenapiFields {
+		fn openapi_fields(&self) -> Vec<crate::OpenapiField>;
+	}
+
+	#[cfg(feature = "openapi")]
+	impl<T: crate::OpenapiFields> WithOpenapiFields for FieldsOf<T> {
+		#[inline]
+		fn openapi_fields(&self) -> Vec<crate::OpenapiField> {
+			T::openapi_fields()
+		}
+	}
+
+	#[cfg(feature = "openapi")]
+	pub trait WithoutOpenapiFields {
+		#[inline]
+		fn openapi_fields(&self) -> Vec<crate::OpenapiField> {
+			Vec::new()
+		}
+	}
+
+	#[cfg(feature = "openapi")]
+	impl<T> WithoutOpenapiFields for &FieldsOf<T> {}
 }
 
 #[cfg(feature = "auth")]
@@ -556,15 +805,41 @@ pub use auth::{
 	AuthHandler, AuthMiddleware, AuthSource, AuthStatus, AuthValidation, StaticAuthHandler
 };
 
+#[cfg(feature = "test-util")]
+mod mock_auth;
+#[cfg(feature = "test-util")]
+pub use mock_auth::MockAuthMiddleware;
+
+#[cfg(feature = "auth")]
+mod csrf;
+#[cfg(feature = "auth")]
+pub use csrf::CsrfConfig;
+
+#[cfg(feature = "client")]
+pub mod client;
+#[cfg(feature = "client")]
+pub use gotham_restful_derive::ResourceClient;
+
 #[cfg(feature = "cors")]
 pub mod cors;
 #[cfg(feature = "cors")]
 pub use cors::{handle_cors, CorsConfig, CorsRoute};
 
 #[cfg(feature = "openapi")]
-mod openapi;
+pub mod openapi;
+#[cfg(feature = "validate-responses")]
+pub use openapi::response_validation::ResponseValidationMode;
 #[cfg(feature = "openapi")]
-pub use openapi::{builder::OpenapiInfo, operation::OperationId, router::GetOpenapi};
+pub use openapi::{
+	builder::{
+		OpenapiContact, OpenapiExternalDocs, OpenapiInfo, OpenapiLicense, OpenapiServer,
+		OpenapiServerVariable, OpenapiVersion, DEFAULT_OPENAPI_DOCUMENT
+	},
+	fields::{OpenapiField, OpenapiFields},
+	operation::{OperationId, OperationIdStyle},
+	router::{GetOpenapi, OpenapiRouter},
+	spec::OpenapiSpec
+};
 
 mod endpoint;
 #[cfg(feature = "openapi")]
@@ -573,11 +848,11 @@ pub use endpoint::{Endpoint, NoopExtractor};
 
 mod response;
 pub use response::{
-	AuthError, AuthErrorOrOther, AuthResult, AuthSuccess, IntoResponse, IntoResponseError,
-	NoContent, Raw, Redirect, Response, Success
+	AuthError, AuthErrorOrOther, AuthFailure, AuthResult, AuthSuccess, IntoResponse,
+	IntoResponseError, NoContent, Raw, Redirect, Response, Success
 };
 #[cfg(feature = "openapi")]
-pub use response::{IntoResponseWithSchema, ResponseSchema};
+pub use response::{IntoResponseWithSchema, ResponseHeader, ResponseSchema};
 
 mod routing;
 pub use routing::{DrawResourceRoutes, DrawResources};
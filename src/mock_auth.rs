@@ -0,0 +1,134 @@
+use crate::{auth::put_status, AuthStatus};
+use gotham::{
+	anyhow,
+	handler::HandlerFuture,
+	hyper::header::{HeaderMap, HeaderName},
+	middleware::{Middleware, NewMiddleware},
+	prelude::*,
+	state::State
+};
+use jsonwebtoken::errors::ErrorKind;
+use serde::de::DeserializeOwned;
+use std::{
+	fmt::{self, Debug},
+	panic::RefUnwindSafe,
+	pin::Pin,
+	sync::Arc
+};
+
+type StatusFn<Data> = dyn Fn(&State) -> AuthStatus<Data> + Send + Sync + RefUnwindSafe;
+
+/// A replacement for the [AuthMiddleware](crate::AuthMiddleware) to be used in tests. Instead of
+/// verifying a token, it determines the [AuthStatus] either from a header that contains the
+/// claims as json, or from a closure. This middleware requires the `test-util` feature.
+///
+/// **Never use this middleware outside of tests**, it allows anyone to authenticate as anyone.
+///
+/// ```rust
+/// # #[macro_use] extern crate gotham_restful_derive;
+/// # use gotham::{
+/// # 	hyper::StatusCode, mime::TEXT_PLAIN, pipeline::*, router::builder::*, test::TestServer
+/// # };
+/// # use gotham_restful::*;
+/// # use serde::Deserialize;
+/// #[derive(Resource)]
+/// #[resource(read_all)]
+/// struct SecretResource;
+///
+/// #[derive(Clone, Deserialize)]
+/// struct AuthData {
+/// 	sub: String
+/// }
+///
+/// #[read_all]
+/// fn read_all(auth: AuthStatus<AuthData>) -> AuthSuccess<Raw<String>> {
+/// 	Ok(Raw::new(auth.ok()?.sub, TEXT_PLAIN))
+/// }
+///
+/// let auth = MockAuthMiddleware::<AuthData>::from_header("x-mock-auth");
+/// let (chain, pipelines) = single_pipeline(new_pipeline().add(auth).build());
+/// let server = TestServer::new(build_router(chain, pipelines, |route| {
+/// 	route.resource::<SecretResource>("secret");
+/// }))
+/// .unwrap();
+///
+/// let res = server
+/// 	.client()
+/// 	.get("http://localhost/secret")
+/// 	.with_header("x-mock-auth", r#"{"sub":"msrd0"}"#.parse().unwrap())
+/// 	.perform()
+/// 	.unwrap();
+/// assert_eq!(res.status(), StatusCode::OK);
+/// assert_eq!(res.read_utf8_body().unwrap(), "msrd0");
+/// ```
+pub struct MockAuthMiddleware<Data: Send + 'static> {
+	status: Arc<StatusFn<Data>>
+}
+
+impl<Data: Send + 'static> Clone for MockAuthMiddleware<Data> {
+	fn clone(&self) -> Self {
+		Self {
+			status: Arc::clone(&self.status)
+		}
+	}
+}
+
+impl<Data: Send + 'static> Debug for MockAuthMiddleware<Data> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MockAuthMiddleware").finish_non_exhaustive()
+	}
+}
+
+impl<Data: Send + 'static> MockAuthMiddleware<Data> {
+	/// Create a new middleware that calls `status` for every request to determine the
+	/// [AuthStatus].
+	pub fn new<F>(status: F) -> Self
+	where
+		F: Fn(&State) -> AuthStatus<Data> + Send + Sync + RefUnwindSafe + 'static
+	{
+		Self {
+			status: Arc::new(status)
+		}
+	}
+
+	/// Create a new middleware that reads the claims as json from the header `name`. Requests
+	/// without that header are [Unauthenticated](AuthStatus::Unauthenticated), and requests
+	/// where the header does not contain valid claims are [Invalid](AuthStatus::Invalid).
+	pub fn from_header<N>(name: N) -> Self
+	where
+		N: TryInto<HeaderName>,
+		N::Error: Debug,
+		Data: DeserializeOwned
+	{
+		let name = name.try_into().expect("Invalid header name");
+		Self::new(move |state| {
+			let header = HeaderMap::try_borrow_from(state).and_then(|map| map.get(&name));
+			match header {
+				None => AuthStatus::Unauthenticated,
+				Some(value) => match serde_json::from_slice(value.as_bytes()) {
+					Ok(data) => AuthStatus::Authenticated(data),
+					Err(_) => AuthStatus::Invalid(ErrorKind::InvalidToken.into())
+				}
+			}
+		})
+	}
+}
+
+impl<Data: Send + 'static> Middleware for MockAuthMiddleware<Data> {
+	fn call<Chain>(self, mut state: State, chain: Chain) -> Pin<Box<HandlerFuture>>
+	where
+		Chain: FnOnce(State) -> Pin<Box<HandlerFuture>>
+	{
+		let status = (self.status)(&state);
+		put_status(&mut state, status);
+		chain(state)
+	}
+}
+
+impl<Data: Send + 'static> NewMiddleware for MockAuthMiddleware<Data> {
+	type Instance = Self;
+
+	fn new_middleware(&self) -> anyhow::Result<Self> {
+		Ok(self.clone())
+	}
+}
@@ -1,14 +1,27 @@
+use super::operation::OperationIdStyle;
+#[cfg(feature = "validate-responses")]
+use super::response_validation::ResponseValidationMode;
+use gotham::hyper::Method;
 use openapi_type::{
 	indexmap::IndexMap,
 	openapiv3::{
-		self, Components, OpenAPI, PathItem, ReferenceOr,
+		self, Components, OpenAPI, Operation, PathItem, ReferenceOr,
 		ReferenceOr::{Item, Reference},
-		Schema, Server
+		Schema, Server, Tag
 	},
 	OpenapiSchema
 };
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{
+	collections::{BTreeMap, HashMap},
+	iter,
+	sync::Arc
+};
+
+/// The name of the OpenAPI document created by `with_openapi`, which can be passed to
+/// [`OpenapiRouter::in_documents`](crate::OpenapiRouter::in_documents) together with the names of
+/// additional documents.
+pub const DEFAULT_OPENAPI_DOCUMENT: &str = "default";
 
 #[derive(Clone, Debug)]
 pub struct OpenapiInfo {
@@ -17,63 +30,304 @@ pub struct OpenapiInfo {
 	pub urls: Vec<String>
 }
 
+/// The contact information of an API.
+#[derive(Clone, Debug, Default)]
+pub struct OpenapiContact {
+	pub name: Option<String>,
+	pub url: Option<String>,
+	pub email: Option<String>
+}
+
+/// The license of an API.
+#[derive(Clone, Debug, Default)]
+pub struct OpenapiLicense {
+	pub name: String,
+	pub url: Option<String>
+}
+
+/// A link to external documentation.
+#[derive(Clone, Debug, Default)]
+pub struct OpenapiExternalDocs {
+	pub url: String,
+	/// A description of the documentation. CommonMark syntax may be used.
+	pub description: Option<String>
+}
+
+/// A server of an API. The url may contain variables in curly braces, like
+/// `https://{region}.example.org/api`, which need to be declared in [`variables`](Self::variables).
+/// Servers are added using [`OpenapiRouter::add_server`](crate::OpenapiRouter::add_server).
+#[derive(Clone, Debug, Default)]
+pub struct OpenapiServer {
+	pub url: String,
+	pub description: Option<String>,
+	pub variables: BTreeMap<String, OpenapiServerVariable>
+}
+
+/// A variable that can be used in the url of an [`OpenapiServer`].
+#[derive(Clone, Debug, Default)]
+pub struct OpenapiServerVariable {
+	/// The value used when no other value is supplied.
+	pub default: String,
+	/// The allowed values of this variable. Leave empty to allow any value.
+	pub enumeration: Vec<String>,
+	pub description: Option<String>
+}
+
+impl From<OpenapiContact> for openapiv3::Contact {
+	fn from(contact: OpenapiContact) -> Self {
+		Self {
+			name: contact.name,
+			url: contact.url,
+			email: contact.email,
+			..Default::default()
+		}
+	}
+}
+
+impl From<OpenapiLicense> for openapiv3::License {
+	fn from(license: OpenapiLicense) -> Self {
+		Self {
+			name: license.name,
+			url: license.url,
+			..Default::default()
+		}
+	}
+}
+
+impl From<OpenapiExternalDocs> for openapiv3::ExternalDocumentation {
+	fn from(docs: OpenapiExternalDocs) -> Self {
+		Self {
+			url: docs.url,
+			description: docs.description,
+			..Default::default()
+		}
+	}
+}
+
+impl From<OpenapiServer> for Server {
+	fn from(server: OpenapiServer) -> Self {
+		let variables = (!server.variables.is_empty()).then(|| {
+			server
+				.variables
+				.into_iter()
+				.map(|(name, var)| {
+					(name, openapiv3::ServerVariable {
+						default: var.default,
+						enumeration: var.enumeration,
+						description: var.description,
+						..Default::default()
+					})
+				})
+				.collect()
+		});
+		Self {
+			url: server.url,
+			description: server.description,
+			variables,
+			..Default::default()
+		}
+	}
+}
+
+/// The version of the OpenAPI specification emitted by the `openapi_spec` handler. The
+/// specification is always collected using the types of OpenAPI 3.0 and converted when it is
+/// being served.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OpenapiVersion {
+	/// OpenAPI 3.0, which uses its own dialect of JSON Schema.
+	#[default]
+	V3_0,
+	/// OpenAPI 3.1, which uses JSON Schema 2020-12. Webhooks are not supported.
+	V3_1
+}
+
+impl OpenapiVersion {
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			Self::V3_0 => "3.0.2",
+			Self::V3_1 => super::v3_1::OPENAPI_3_1
+		}
+	}
+}
+
+impl From<OpenapiInfo> for OpenAPI {
+	fn from(info: OpenapiInfo) -> Self {
+		Self {
+			openapi: OpenapiVersion::V3_0.as_str().to_owned(),
+			info: openapiv3::Info {
+				title: info.title,
+				version: info.version,
+				..Default::default()
+			},
+			servers: info
+				.urls
+				.into_iter()
+				.map(|url| Server {
+					url,
+					..Default::default()
+				})
+				.collect(),
+			..Default::default()
+		}
+	}
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct OpenapiBuilder {
-	pub(crate) openapi: Arc<RwLock<OpenAPI>>
+	/// The document that endpoints are added to, and that is served by the specification handlers.
+	pub(crate) openapi: Arc<RwLock<OpenAPI>>,
+	/// Further documents that endpoints are added to.
+	additional: Vec<Arc<RwLock<OpenAPI>>>,
+	/// All documents by name, shared by all builders of the same `with_openapi` block.
+	named: Arc<RwLock<HashMap<String, Arc<RwLock<OpenAPI>>>>>,
+	/// The naming convention of automatically generated operation ids.
+	pub(crate) operation_id_style: OperationIdStyle,
+	/// Whether requests should be validated against the schema of their endpoint.
+	pub(crate) validate_requests: bool,
+	/// Whether and how responses should be validated against the schema of their endpoint.
+	#[cfg(feature = "validate-responses")]
+	pub(crate) validate_responses: Option<ResponseValidationMode>
 }
 
 impl OpenapiBuilder {
 	pub(crate) fn new(info: OpenapiInfo) -> Self {
+		let openapi = Arc::new(RwLock::new(info.into()));
+		let named = HashMap::from([(DEFAULT_OPENAPI_DOCUMENT.to_owned(), Arc::clone(&openapi))]);
 		Self {
-			openapi: Arc::new(RwLock::new(OpenAPI {
-				openapi: "3.0.2".to_string(),
-				info: openapiv3::Info {
-					title: info.title,
-					version: info.version,
-					..Default::default()
-				},
-				servers: info
-					.urls
-					.into_iter()
-					.map(|url| Server {
-						url,
-						..Default::default()
-					})
-					.collect(),
-				..Default::default()
-			}))
+			openapi,
+			additional: Vec::new(),
+			named: Arc::new(RwLock::new(named)),
+			operation_id_style: OperationIdStyle::default(),
+			validate_requests: false,
+			#[cfg(feature = "validate-responses")]
+			validate_responses: None
 		}
 	}
 
-	/// Remove path from the OpenAPI spec, or return an empty one if not included. This is handy if you need to
-	/// modify the path and add it back after the modification
-	pub(crate) fn remove_path(&mut self, path: &str) -> PathItem {
-		let mut openapi = self.openapi.write();
-		match openapi.paths.paths.swap_remove(path) {
-			Some(Item(item)) => item,
-			_ => PathItem::default()
+	fn documents(&self) -> impl Iterator<Item = &Arc<RwLock<OpenAPI>>> {
+		iter::once(&self.openapi).chain(&self.additional)
+	}
+
+	/// Create an additional document called `name`.
+	pub(crate) fn add_document(&mut self, name: &str, info: OpenapiInfo) {
+		let mut named = self.named.write();
+		if named.contains_key(name) {
+			panic!("An OpenAPI document called {name:?} already exists");
 		}
+		named.insert(name.to_owned(), Arc::new(RwLock::new(info.into())));
 	}
 
-	pub(crate) fn add_path<Path: ToString>(&mut self, path: Path, item: PathItem) {
-		let mut openapi = self.openapi.write();
-		openapi.paths.paths.insert(path.to_string(), Item(item));
+	/// Return a builder that adds endpoints to the documents called `names`, the first of which
+	/// is served by the specification handlers.
+	pub(crate) fn select_documents(&self, names: &[&str]) -> Self {
+		let named = self.named.read();
+		let mut documents = names.iter().map(|name| match named.get(*name) {
+			Some(openapi) => Arc::clone(openapi),
+			None => panic!("There is no OpenAPI document called {name:?}")
+		});
+		let Some(openapi) = documents.next() else {
+			panic!("At least one OpenAPI document needs to be selected");
+		};
+		let additional = documents.collect();
+		Self {
+			openapi,
+			additional,
+			..self.clone()
+		}
 	}
 
-	fn add_schema_impl(&mut self, name: String, mut schema: OpenapiSchema) {
-		self.add_schema_dependencies(&mut schema.dependencies);
+	/// Return a builder that adds endpoints to a new document that is not served anywhere.
+	pub(crate) fn detached(&self) -> Self {
+		Self {
+			openapi: Default::default(),
+			additional: Vec::new(),
+			..self.clone()
+		}
+	}
 
-		let mut openapi = self.openapi.write();
-		match &mut openapi.components {
-			Some(comp) => {
-				comp.schemas.insert(name, Item(schema.schema));
-			},
-			None => {
-				let mut comp = Components::default();
-				comp.schemas.insert(name, Item(schema.schema));
-				openapi.components = Some(comp);
+	/// Add `operation` for `method` at `path` to all documents.
+	pub(crate) fn add_operation(&mut self, path: &str, method: &Method, operation: Operation) {
+		let op: fn(&mut PathItem) -> &mut Option<Operation> = match *method {
+			Method::GET => |item| &mut item.get,
+			Method::PUT => |item| &mut item.put,
+			Method::POST => |item| &mut item.post,
+			Method::DELETE => |item| &mut item.delete,
+			Method::OPTIONS => |item| &mut item.options,
+			Method::HEAD => |item| &mut item.head,
+			Method::PATCH => |item| &mut item.patch,
+			Method::TRACE => |item| &mut item.trace,
+			_ => {
+				warn!("Ignoring unsupported method '{method}' in OpenAPI Specification");
+				return;
 			}
 		};
+		for openapi in self.documents() {
+			let mut openapi = openapi.write();
+			let mut item = match openapi.paths.paths.swap_remove(path) {
+				Some(Item(item)) => item,
+				_ => PathItem::default()
+			};
+			*op(&mut item) = Some(operation.clone());
+			openapi.paths.paths.insert(path.to_owned(), Item(item));
+		}
+	}
+
+	/// Return the operation ids of all operations except for `method` at `path`, together with a
+	/// description of the operation that uses them.
+	pub(crate) fn operation_ids(&self, path: &str, method: &Method) -> HashMap<String, String> {
+		let method = method.as_str().to_lowercase();
+		let mut ids = HashMap::new();
+		for openapi in self.documents() {
+			let openapi = openapi.read();
+			for (item_path, item) in openapi.paths.iter() {
+				let Item(item) = item else {
+					continue;
+				};
+				for (item_method, op) in item.iter() {
+					if item_path == path && item_method == method {
+						continue;
+					}
+					if let Some(id) = &op.operation_id {
+						let op = format!("{} {item_path}", item_method.to_uppercase());
+						ids.insert(id.clone(), op);
+					}
+				}
+			}
+		}
+		ids
+	}
+
+	/// Add a top-level tag, unless a tag with the same name was added before.
+	pub(crate) fn add_tag(&mut self, name: &str, description: Option<&str>) {
+		for openapi in self.documents() {
+			let mut openapi = openapi.write();
+			match openapi.tags.iter_mut().find(|tag| tag.name == name) {
+				Some(tag) => {
+					if tag.description.is_none() {
+						tag.description = description.map(String::from);
+					}
+				},
+				None => openapi.tags.push(Tag {
+					name: name.to_owned(),
+					description: description.map(String::from),
+					..Default::default()
+				})
+			}
+		}
+	}
+
+	fn add_schema_impl(&mut self, name: String, mut schema: OpenapiSchema) {
+		self.add_schema_dependencies(&mut schema.dependencies);
+
+		for openapi in self.documents() {
+			let mut openapi = openapi.write();
+			openapi
+				.components
+				.get_or_insert_with(Components::default)
+				.schemas
+				.insert(name.clone(), Item(schema.schema.clone()));
+		}
 	}
 
 	fn add_schema_dependencies(&mut self, dependencies: &mut IndexMap<String, OpenapiSchema>) {
@@ -131,7 +385,7 @@ mod test {
 	}
 
 	fn openapi(builder: OpenapiBuilder) -> OpenAPI {
-		Arc::try_unwrap(builder.openapi).unwrap().into_inner()
+		builder.openapi.read().clone()
 	}
 
 	#[test]
@@ -0,0 +1,873 @@
+use super::spec::OpenapiSpec;
+use openapi_type::openapiv3::OpenAPI;
+use serde_json::{Map, Value};
+use std::{
+	collections::HashSet,
+	fmt::{self, Display},
+	fs::File,
+	path::Path,
+	sync::OnceLock
+};
+
+const METHODS: [&str; 8] = [
+	"get", "put", "post", "delete", "options", "head", "patch", "trace"
+];
+
+/// The kind of a [Change] between two OpenAPI specifications.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+	PathAdded,
+	PathRemoved,
+	OperationAdded,
+	OperationRemoved,
+	ParameterAdded { required: bool },
+	ParameterRemoved,
+	ParameterRequired,
+	ParameterOptional,
+	RequestBodyAdded { required: bool },
+	RequestBodyRemoved,
+	RequestBodyRequired,
+	RequestBodyOptional,
+	ContentTypeAdded(String),
+	ContentTypeRemoved(String),
+	ResponseAdded(String),
+	ResponseRemoved(String),
+	PropertyAdded { required: bool },
+	PropertyRemoved { required: bool },
+	PropertyRequired,
+	PropertyOptional,
+	EnumValueAdded(Value),
+	EnumValueRemoved(Value),
+	TypeChanged { old: String, new: String },
+	NullableAdded,
+	NullableRemoved
+}
+
+impl Display for ChangeKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let required = |required: &bool| match required {
+			true => "required",
+			false => "optional"
+		};
+		match self {
+			Self::PathAdded => f.write_str("path added"),
+			Self::PathRemoved => f.write_str("path removed"),
+			Self::OperationAdded => f.write_str("operation added"),
+			Self::OperationRemoved => f.write_str("operation removed"),
+			Self::ParameterAdded { required: r } => write!(f, "{} parameter added", required(r)),
+			Self::ParameterRemoved => f.write_str("parameter removed"),
+			Self::ParameterRequired => f.write_str("parameter became required"),
+			Self::ParameterOptional => f.write_str("parameter became optional"),
+			Self::RequestBodyAdded { required: r } => {
+				write!(f, "{} request body added", required(r))
+			},
+			Self::RequestBodyRemoved => f.write_str("request body removed"),
+			Self::RequestBodyRequired => f.write_str("request body became required"),
+			Self::RequestBodyOptional => f.write_str("request body became optional"),
+			Self::ContentTypeAdded(mime) => write!(f, "content type `{mime}` added"),
+			Self::ContentTypeRemoved(mime) => write!(f, "content type `{mime}` removed"),
+			Self::ResponseAdded(status) => write!(f, "response `{status}` added"),
+			Self::ResponseRemoved(status) => write!(f, "response `{status}` removed"),
+			Self::PropertyAdded { required: r } => write!(f, "{} property added", required(r)),
+			Self::PropertyRemoved { required: r } => {
+				write!(f, "{} property removed", required(r))
+			},
+			Self::PropertyRequired => f.write_str("property became required"),
+			Self::PropertyOptional => f.write_str("property became optional"),
+			Self::EnumValueAdded(value) => write!(f, "enum value `{value}` added"),
+			Self::EnumValueRemoved(value) => write!(f, "enum value `{value}` removed"),
+			Self::TypeChanged { old, new } => write!(f, "type changed from `{old}` to `{new}`"),
+			Self::NullableAdded => f.write_str("became nullable"),
+			Self::NullableRemoved => f.write_str("is no longer nullable")
+		}
+	}
+}
+
+/// A change between two OpenAPI specifications.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+	/// The location of the change, like `GET /foo` for an operation, or
+	/// `POST /foo request body application/json#/bar` for a property of the request body. Array
+	/// items are denoted as `[]`, and additional properties as `{}`.
+	pub location: String,
+	/// The kind of the change.
+	pub kind: ChangeKind
+}
+
+impl Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.location, self.kind)
+	}
+}
+
+/// All changes between two OpenAPI specifications, as returned by [diff].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OpenapiDiff {
+	/// Changes that can break existing clients.
+	pub breaking: Vec<Change>,
+	/// Changes that existing clients are not affected by, like additions.
+	pub non_breaking: Vec<Change>
+}
+
+impl OpenapiDiff {
+	/// Returns `true` if there are no changes at all.
+	pub fn is_empty(&self) -> bool {
+		self.breaking.is_empty() && self.non_breaking.is_empty()
+	}
+
+	/// Returns `true` if any change can break existing clients.
+	pub fn is_breaking(&self) -> bool {
+		!self.breaking.is_empty()
+	}
+}
+
+impl Display for OpenapiDiff {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for (title, changes) in [
+			("Breaking changes", &self.breaking),
+			("Non-breaking changes", &self.non_breaking)
+		] {
+			if changes.is_empty() {
+				continue;
+			}
+			writeln!(f, "{title}:")?;
+			for change in changes {
+				writeln!(f, " - {change}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Compare two OpenAPI specifications and report all changes, sorted into breaking and
+/// non-breaking ones. A change is breaking if a client written against `old` might no longer
+/// work with a server implementing `new`.
+pub fn diff(old: &OpenAPI, new: &OpenAPI) -> OpenapiDiff {
+	let old = serde_json::to_value(old).expect("Failed to serialize OpenAPI specification");
+	let new = serde_json::to_value(new).expect("Failed to serialize OpenAPI specification");
+	diff_values(&old, &new)
+}
+
+/// Assert that `spec` does not contain any breaking changes compared to the json specification
+/// stored in the `snapshot` file. This is intended to be called from a test, the snapshot is
+/// usually the output of [OpenapiSpec::to_json] for the last release of your API.
+///
+/// # Panics
+///
+/// This function panics if the snapshot cannot be read, or if there are breaking changes.
+pub fn assert_no_breaking_changes<P: AsRef<Path>>(snapshot: P, spec: &OpenapiSpec) {
+	let snapshot = snapshot.as_ref();
+	let old: Value = File::open(snapshot)
+		.map_err(serde_json::Error::io)
+		.and_then(serde_json::from_reader)
+		.unwrap_or_else(|err| panic!("Failed to read {}: {err}", snapshot.display()));
+	let new: Value = spec
+		.to_json()
+		.and_then(|json| serde_json::from_str(&json))
+		.expect("Failed to serialize OpenAPI specification");
+
+	let diff = diff_values(&old, &new);
+	if diff.is_breaking() {
+		panic!(
+			"The OpenAPI specification contains breaking changes compared to {}:\n{diff}",
+			snapshot.display()
+		);
+	}
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Direction {
+	Request,
+	Response
+}
+
+struct Differ<'a> {
+	old_root: &'a Value,
+	new_root: &'a Value,
+	/// The schemas that are currently being compared, to stop at recursive types.
+	visiting: HashSet<(*const Value, *const Value)>,
+	diff: OpenapiDiff
+}
+
+fn diff_values(old: &Value, new: &Value) -> OpenapiDiff {
+	let mut differ = Differ {
+		old_root: old,
+		new_root: new,
+		visiting: HashSet::new(),
+		diff: OpenapiDiff::default()
+	};
+	differ.paths();
+	differ.diff
+}
+
+fn empty() -> &'static Map<String, Value> {
+	static EMPTY: OnceLock<Map<String, Value>> = OnceLock::new();
+	EMPTY.get_or_init(Map::new)
+}
+
+fn object<'v>(value: &'v Value, key: &str) -> &'v Map<String, Value> {
+	value.get(key).and_then(Value::as_object).unwrap_or(empty())
+}
+
+fn is_required(value: &Value) -> bool {
+	value.get("required") == Some(&Value::Bool(true))
+}
+
+/// Follow `$ref` references within the document `root`.
+fn resolve<'v>(root: &'v Value, mut value: &'v Value) -> &'v Value {
+	// limit the depth in case of reference cycles
+	for _ in 0..32 {
+		match value
+			.get("$ref")
+			.and_then(Value::as_str)
+			.and_then(|reference| reference.strip_prefix('#'))
+			.and_then(|pointer| root.pointer(pointer))
+		{
+			Some(target) => value = target,
+			None => break
+		}
+	}
+	value
+}
+
+/// Resolve the schema and remove wrappers that only add nullability, returning the schema and
+/// whether it is nullable.
+fn normalize<'v>(root: &'v Value, mut schema: &'v Value) -> (&'v Value, bool) {
+	let mut nullable = false;
+	for _ in 0..32 {
+		schema = resolve(root, schema);
+		nullable |= schema.get("nullable") == Some(&Value::Bool(true));
+		if let Some(Value::Array(types)) = schema.get("type") {
+			nullable |= types.iter().any(|ty| ty == "null");
+		}
+
+		let obj = match schema.as_object() {
+			Some(obj) => obj,
+			None => break
+		};
+		if obj.contains_key("type") || obj.contains_key("properties") {
+			break;
+		}
+		match (obj.get("allOf"), obj.get("anyOf")) {
+			(Some(Value::Array(all_of)), _) if all_of.len() == 1 => schema = &all_of[0],
+			(_, Some(Value::Array(any_of))) if any_of.len() == 2 => {
+				let is_null = |schema: &Value| schema.get("type").is_some_and(|ty| ty == "null");
+				match (is_null(&any_of[0]), is_null(&any_of[1])) {
+					(false, true) => schema = &any_of[0],
+					(true, false) => schema = &any_of[1],
+					_ => break
+				}
+				nullable = true;
+			},
+			_ => break
+		}
+	}
+	(schema, nullable)
+}
+
+fn type_name(schema: &Value) -> String {
+	match schema.get("type") {
+		Some(Value::String(ty)) => return ty.clone(),
+		Some(Value::Array(types)) => {
+			return types
+				.iter()
+				.filter_map(Value::as_str)
+				.filter(|ty| *ty != "null")
+				.collect::<Vec<_>>()
+				.join("|")
+		},
+		_ => {}
+	}
+	if schema.get("properties").is_some() {
+		return "object".to_owned();
+	}
+	for key in ["allOf", "anyOf", "oneOf"] {
+		if let Some(Value::Array(schemas)) = schema.get(key) {
+			return format!("{key} with {} schemas", schemas.len());
+		}
+	}
+	"any".to_owned()
+}
+
+fn escape(name: &str) -> String {
+	name.replace('~', "~0").replace('/', "~1")
+}
+
+impl<'a> Differ<'a> {
+	fn push(&mut self, breaking: bool, location: &str, kind: ChangeKind) {
+		let change = Change {
+			location: location.to_owned(),
+			kind
+		};
+		match breaking {
+			true => self.diff.breaking.push(change),
+			false => self.diff.non_breaking.push(change)
+		}
+	}
+
+	fn paths(&mut self) {
+		let old_paths = object(self.old_root, "paths");
+		let new_paths = object(self.new_root, "paths");
+
+		for (path, old) in old_paths {
+			match new_paths.get(path) {
+				Some(new) => self.path(path, old, new),
+				None => self.push(true, path, ChangeKind::PathRemoved)
+			}
+		}
+		for path in new_paths.keys() {
+			if !old_paths.contains_key(path) {
+				self.push(false, path, ChangeKind::PathAdded);
+			}
+		}
+	}
+
+	fn path(&mut self, path: &str, old: &'a Value, new: &'a Value) {
+		let old = resolve(self.old_root, old);
+		let new = resolve(self.new_root, new);
+
+		for method in METHODS {
+			let location = format!("{} {path}", method.to_uppercase());
+			match (old.get(method), new.get(method)) {
+				(Some(old_op), Some(new_op)) => {
+					let old_params = old
+						.get("parameters")
+						.into_iter()
+						.chain(old_op.get("parameters"));
+					let new_params = new
+						.get("parameters")
+						.into_iter()
+						.chain(new_op.get("parameters"));
+					self.parameters(&location, old_params, new_params);
+					self.request_body(
+						&location,
+						old_op.get("requestBody"),
+						new_op.get("requestBody")
+					);
+					self.responses(
+						&location,
+						object(old_op, "responses"),
+						object(new_op, "responses")
+					);
+				},
+				(Some(_), None) => self.push(true, &location, ChangeKind::OperationRemoved),
+				(None, Some(_)) => self.push(false, &location, ChangeKind::OperationAdded),
+				(None, None) => {}
+			}
+		}
+	}
+
+	fn parameters<I>(&mut self, location: &str, old: I, new: I)
+	where
+		I: Iterator<Item = &'a Value>
+	{
+		let collect = |root: &'a Value, params: I| -> Vec<(String, &'a Value)> {
+			params
+				.filter_map(Value::as_array)
+				.flatten()
+				.map(|param| resolve(root, param))
+				.map(|param| {
+					let name = param
+						.get("name")
+						.and_then(Value::as_str)
+						.unwrap_or_default();
+					let location = param.get("in").and_then(Value::as_str).unwrap_or_default();
+					(format!("{location} parameter {name}"), param)
+				})
+				.collect()
+		};
+		let old = collect(self.old_root, old);
+		let new = collect(self.new_root, new);
+
+		for (name, old_param) in &old {
+			let location = format!("{location} {name}");
+			match new.iter().find(|(new_name, _)| new_name == name) {
+				Some((_, new_param)) => {
+					match (is_required(old_param), is_required(new_param)) {
+						(false, true) => self.push(true, &location, ChangeKind::ParameterRequired),
+						(true, false) => self.push(false, &location, ChangeKind::ParameterOptional),
+						_ => {}
+					}
+					if let (Some(old_schema), Some(new_schema)) =
+						(old_param.get("schema"), new_param.get("schema"))
+					{
+						self.schema(&location, old_schema, new_schema, Direction::Request);
+					}
+				},
+				None => self.push(false, &location, ChangeKind::ParameterRemoved)
+			}
+		}
+		for (name, new_param) in &new {
+			if !old.iter().any(|(old_name, _)| old_name == name) {
+				let required = is_required(new_param);
+				let location = format!("{location} {name}");
+				self.push(required, &location, ChangeKind::ParameterAdded { required });
+			}
+		}
+	}
+
+	fn request_body(&mut self, location: &str, old: Option<&'a Value>, new: Option<&'a Value>) {
+		let location = format!("{location} request body");
+		match (old, new) {
+			(Some(old), Some(new)) => {
+				let old = resolve(self.old_root, old);
+				let new = resolve(self.new_root, new);
+				match (is_required(old), is_required(new)) {
+					(false, true) => self.push(true, &location, ChangeKind::RequestBodyRequired),
+					(true, false) => self.push(false, &location, ChangeKind::RequestBodyOptional),
+					_ => {}
+				}
+				self.content(&location, old, new, Direction::Request);
+			},
+			(Some(_), None) => self.push(true, &location, ChangeKind::RequestBodyRemoved),
+			(None, Some(new)) => {
+				let required = is_required(resolve(self.new_root, new));
+				self.push(required, &location, ChangeKind::RequestBodyAdded {
+					required
+				});
+			},
+			(None, None) => {}
+		}
+	}
+
+	fn responses(
+		&mut self,
+		location: &str,
+		old: &'a Map<String, Value>,
+		new: &'a Map<String, Value>
+	) {
+		for (status, old_res) in old {
+			match new.get(status) {
+				Some(new_res) => {
+					let location = format!("{location} response {status}");
+					let old_res = resolve(self.old_root, old_res);
+					let new_res = resolve(self.new_root, new_res);
+					self.content(&location, old_res, new_res, Direction::Response);
+				},
+				None => self.push(true, location, ChangeKind::ResponseRemoved(status.clone()))
+			}
+		}
+		for status in new.keys() {
+			if !old.contains_key(status) {
+				self.push(false, location, ChangeKind::ResponseAdded(status.clone()));
+			}
+		}
+	}
+
+	fn content(&mut self, location: &str, old: &'a Value, new: &'a Value, dir: Direction) {
+		let old = object(old, "content");
+		let new = object(new, "content");
+
+		for (mime, old_media) in old {
+			match new.get(mime) {
+				Some(new_media) => {
+					if let (Some(old_schema), Some(new_schema)) =
+						(old_media.get("schema"), new_media.get("schema"))
+					{
+						let location = format!("{location} {mime}#");
+						self.schema(&location, old_schema, new_schema, dir);
+					}
+				},
+				None => self.push(true, location, ChangeKind::ContentTypeRemoved(mime.clone()))
+			}
+		}
+		for mime in new.keys() {
+			if !old.contains_key(mime) {
+				self.push(false, location, ChangeKind::ContentTypeAdded(mime.clone()));
+			}
+		}
+	}
+
+	fn schema(&mut self, location: &str, old: &'a Value, new: &'a Value, dir: Direction) {
+		let (old, old_nullable) = normalize(self.old_root, old);
+		let (new, new_nullable) = normalize(self.new_root, new);
+
+		let key = (old as *const Value, new as *const Value);
+		if !self.visiting.insert(key) {
+			return;
+		}
+		self.schema_inner(location, old, new, dir, old_nullable, new_nullable);
+		self.visiting.remove(&key);
+	}
+
+	fn schema_inner(
+		&mut self,
+		location: &str,
+		old: &'a Value,
+		new: &'a Value,
+		dir: Direction,
+		old_nullable: bool,
+		new_nullable: bool
+	) {
+		let request = dir == Direction::Request;
+		let response = dir == Direction::Response;
+
+		match (old_nullable, new_nullable) {
+			(false, true) => self.push(response, location, ChangeKind::NullableAdded),
+			(true, false) => self.push(request, location, ChangeKind::NullableRemoved),
+			_ => {}
+		}
+
+		let (old_ty, new_ty) = (type_name(old), type_name(new));
+		if old_ty != new_ty {
+			self.push(true, location, ChangeKind::TypeChanged {
+				old: old_ty,
+				new: new_ty
+			});
+			return;
+		}
+
+		// clients must be able to send all values they used to send, and must be able to
+		// understand all values they receive
+		if let (Some(Value::Array(old_enum)), Some(Value::Array(new_enum))) =
+			(old.get("enum"), new.get("enum"))
+		{
+			for value in old_enum.iter().filter(|value| !new_enum.contains(value)) {
+				self.push(
+					request,
+					location,
+					ChangeKind::EnumValueRemoved(value.clone())
+				);
+			}
+			for value in new_enum.iter().filter(|value| !old_enum.contains(value)) {
+				self.push(
+					response,
+					location,
+					ChangeKind::EnumValueAdded(value.clone())
+				);
+			}
+		}
+
+		let old_props = object(old, "properties");
+		let new_props = object(new, "properties");
+		let required = |schema: &Value, name: &str| {
+			schema
+				.get("required")
+				.and_then(Value::as_array)
+				.is_some_and(|required| required.iter().any(|req| req == name))
+		};
+		for (name, old_prop) in old_props {
+			let location = format!("{location}/{}", escape(name));
+			let old_required = required(old, name);
+			match new_props.get(name) {
+				Some(new_prop) => {
+					match (old_required, required(new, name)) {
+						(false, true) => {
+							self.push(request, &location, ChangeKind::PropertyRequired)
+						},
+						(true, false) => {
+							self.push(response, &location, ChangeKind::PropertyOptional)
+						},
+						_ => {}
+					}
+					self.schema(&location, old_prop, new_prop, dir);
+				},
+				None => self.push(
+					response && old_required,
+					&location,
+					ChangeKind::PropertyRemoved {
+						required: old_required
+					}
+				)
+			}
+		}
+		for name in new_props.keys() {
+			if !old_props.contains_key(name) {
+				let location = format!("{location}/{}", escape(name));
+				let new_required = required(new, name);
+				self.push(
+					request && new_required,
+					&location,
+					ChangeKind::PropertyAdded {
+						required: new_required
+					}
+				);
+			}
+		}
+
+		if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+			self.schema(&format!("{location}/[]"), old_items, new_items, dir);
+		}
+		if let (Some(old_additional @ Value::Object(_)), Some(new_additional @ Value::Object(_))) = (
+			old.get("additionalProperties"),
+			new.get("additionalProperties")
+		) {
+			self.schema(
+				&format!("{location}/{{}}"),
+				old_additional,
+				new_additional,
+				dir
+			);
+		}
+		for key in ["allOf", "anyOf", "oneOf"] {
+			if let (Some(Value::Array(old_schemas)), Some(Value::Array(new_schemas))) =
+				(old.get(key), new.get(key))
+			{
+				for (i, (old_schema, new_schema)) in old_schemas.iter().zip(new_schemas).enumerate()
+				{
+					self.schema(
+						&format!("{location}/{key}/{i}"),
+						old_schema,
+						new_schema,
+						dir
+					);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	fn spec(paths: Value, schemas: Value) -> Value {
+		json!({
+			"openapi": "3.0.2",
+			"info": { "title": "Test", "version": "1.0.0" },
+			"paths": paths,
+			"components": { "schemas": schemas }
+		})
+	}
+
+	fn change(location: &str, kind: ChangeKind) -> Change {
+		Change {
+			location: location.to_owned(),
+			kind
+		}
+	}
+
+	#[test]
+	fn no_changes() {
+		let old = spec(
+			json!({ "/foo": { "get": { "responses": { "204": { "description": "" } } } } }),
+			json!({})
+		);
+		let diff = diff_values(&old, &old);
+		assert!(diff.is_empty());
+	}
+
+	#[test]
+	fn paths_and_operations() {
+		let old = spec(
+			json!({
+				"/foo": {
+					"get": { "responses": {} },
+					"delete": { "responses": {} }
+				},
+				"/bar": { "get": { "responses": {} } }
+			}),
+			json!({})
+		);
+		let new = spec(
+			json!({
+				"/foo": {
+					"get": { "responses": {} },
+					"put": { "responses": {} }
+				},
+				"/baz": { "get": { "responses": {} } }
+			}),
+			json!({})
+		);
+		let diff = diff_values(&old, &new);
+		assert_eq!(diff.breaking, vec![
+			change("/bar", ChangeKind::PathRemoved),
+			change("DELETE /foo", ChangeKind::OperationRemoved)
+		]);
+		assert_eq!(diff.non_breaking, vec![
+			change("PUT /foo", ChangeKind::OperationAdded),
+			change("/baz", ChangeKind::PathAdded)
+		]);
+	}
+
+	#[test]
+	fn parameters_and_responses() {
+		let old = spec(
+			json!({
+				"/foo": {
+					"get": {
+						"parameters": [
+							{ "in": "query", "name": "limit", "schema": { "type": "integer" } }
+						],
+						"responses": {
+							"200": { "description": "" },
+							"404": { "description": "" }
+						}
+					}
+				}
+			}),
+			json!({})
+		);
+		let new = spec(
+			json!({
+				"/foo": {
+					"get": {
+						"parameters": [
+							{ "in": "query", "name": "limit", "schema": { "type": "string" } },
+							{ "in": "query", "name": "page", "required": true, "schema": { "type": "integer" } },
+							{ "in": "header", "name": "x-foo", "schema": { "type": "string" } }
+						],
+						"responses": {
+							"200": { "description": "" },
+							"500": { "description": "" }
+						}
+					}
+				}
+			}),
+			json!({})
+		);
+		let diff = diff_values(&old, &new);
+		assert_eq!(diff.breaking, vec![
+			change("GET /foo query parameter limit", ChangeKind::TypeChanged {
+				old: "integer".to_owned(),
+				new: "string".to_owned()
+			}),
+			change(
+				"GET /foo query parameter page",
+				ChangeKind::ParameterAdded { required: true }
+			),
+			change("GET /foo", ChangeKind::ResponseRemoved("404".to_owned()))
+		]);
+		assert_eq!(diff.non_breaking, vec![
+			change(
+				"GET /foo header parameter x-foo",
+				ChangeKind::ParameterAdded { required: false }
+			),
+			change("GET /foo", ChangeKind::ResponseAdded("500".to_owned()))
+		]);
+	}
+
+	#[test]
+	fn schemas() {
+		let paths = json!({
+			"/foo": {
+				"post": {
+					"requestBody": {
+						"required": true,
+						"content": {
+							"application/json": {
+								"schema": { "$ref": "#/components/schemas/Foo" }
+							}
+						}
+					},
+					"responses": {
+						"200": {
+							"description": "",
+							"content": {
+								"application/json": {
+									"schema": {
+										"type": "array",
+										"items": { "$ref": "#/components/schemas/Foo" }
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		});
+		let old = spec(
+			paths.clone(),
+			json!({
+				"Foo": {
+					"type": "object",
+					"properties": {
+						"kind": { "type": "string", "enum": ["a", "b"] },
+						"name": { "type": "string" },
+						"child": { "$ref": "#/components/schemas/Foo" }
+					},
+					"required": ["kind", "name"]
+				}
+			})
+		);
+		let new = spec(
+			paths,
+			json!({
+				"Foo": {
+					"type": "object",
+					"properties": {
+						"kind": { "type": "string", "enum": ["a", "c"] },
+						"size": { "type": "integer" },
+						"child": { "$ref": "#/components/schemas/Foo" }
+					},
+					"required": ["kind", "size"]
+				}
+			})
+		);
+		let diff = diff_values(&old, &new);
+		let body = "POST /foo request body application/json#";
+		let res = "POST /foo response 200 application/json#/[]";
+		assert_eq!(diff.breaking, vec![
+			change(
+				&format!("{body}/kind"),
+				ChangeKind::EnumValueRemoved(json!("b"))
+			),
+			change(&format!("{body}/size"), ChangeKind::PropertyAdded {
+				required: true
+			}),
+			change(
+				&format!("{res}/kind"),
+				ChangeKind::EnumValueAdded(json!("c"))
+			),
+			change(&format!("{res}/name"), ChangeKind::PropertyRemoved {
+				required: true
+			})
+		]);
+		assert_eq!(diff.non_breaking, vec![
+			change(
+				&format!("{body}/kind"),
+				ChangeKind::EnumValueAdded(json!("c"))
+			),
+			change(&format!("{body}/name"), ChangeKind::PropertyRemoved {
+				required: true
+			}),
+			change(
+				&format!("{res}/kind"),
+				ChangeKind::EnumValueRemoved(json!("b"))
+			),
+			change(&format!("{res}/size"), ChangeKind::PropertyAdded {
+				required: true
+			})
+		]);
+	}
+
+	#[test]
+	fn nullable() {
+		let schema = |schema: Value| {
+			spec(
+				json!({
+					"/foo": {
+						"get": {
+							"responses": {
+								"200": {
+									"description": "",
+									"content": { "application/json": { "schema": schema } }
+								}
+							}
+						}
+					}
+				}),
+				json!({ "Foo": { "type": "object", "properties": {} } })
+			)
+		};
+		let old = schema(json!({ "$ref": "#/components/schemas/Foo" }));
+		let new_v3_0 = schema(json!({
+			"allOf": [{ "$ref": "#/components/schemas/Foo" }],
+			"nullable": true
+		}));
+		let new_v3_1 = schema(json!({
+			"anyOf": [{ "$ref": "#/components/schemas/Foo" }, { "type": "null" }]
+		}));
+
+		for new in [new_v3_0, new_v3_1] {
+			let diff = diff_values(&old, &new);
+			assert_eq!(diff.breaking, vec![change(
+				"GET /foo response 200 application/json#",
+				ChangeKind::NullableAdded
+			)]);
+			assert!(diff.non_breaking.is_empty());
+		}
+	}
+}
@@ -0,0 +1,89 @@
+use openapi_type::{
+	openapiv3::{ReferenceOr::Item, SchemaKind, Type},
+	OpenapiSchema
+};
+use serde_json::Value;
+
+/// The example and deprecation of a field. The `OpenapiType` derive cannot describe these, so
+/// they are added to the schema of the field afterwards. See [OpenapiFields].
+#[derive(Debug)]
+pub struct OpenapiField {
+	/// The name of the field in the schema, after applying any renaming.
+	pub name: &'static str,
+	/// An example value of the field.
+	pub example: Option<Value>,
+	/// Whether the field is deprecated.
+	pub deprecated: bool
+}
+
+/// This trait provides examples and deprecation of fields of path parameter, query parameter and
+/// request body types. It is implemented by the [`openapi_fields`](crate::openapi_fields)
+/// attribute, which reads `#[openapi(example = ...)]` and `#[deprecated]` from the fields.
+pub trait OpenapiFields {
+	/// The fields that have an example or are deprecated.
+	fn openapi_fields() -> Vec<OpenapiField>;
+}
+
+/// Add the example and deprecation of `fields` to the properties of `schema`. Properties that
+/// are references to other schemas are left untouched, as a reference cannot have any siblings.
+pub(crate) fn add_fields(schema: &mut OpenapiSchema, fields: Vec<OpenapiField>) {
+	let SchemaKind::Type(Type::Object(obj)) = &mut schema.schema.schema_kind else {
+		return;
+	};
+	for field in fields {
+		let Some(Item(property)) = obj.properties.get_mut(field.name) else {
+			continue;
+		};
+		let data = &mut property.schema_data;
+		if field.example.is_some() {
+			data.example = field.example;
+		}
+		data.deprecated |= field.deprecated;
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use openapi_type::OpenapiType;
+	use serde_json::json;
+
+	#[derive(OpenapiType)]
+	#[allow(dead_code)]
+	struct Foo {
+		bar: String,
+		baz: Option<u64>
+	}
+
+	#[test]
+	fn add_example_and_deprecation() {
+		let mut schema = Foo::schema();
+		add_fields(&mut schema, vec![
+			OpenapiField {
+				name: "bar",
+				example: Some(json!("foobar")),
+				deprecated: false
+			},
+			OpenapiField {
+				name: "baz",
+				example: None,
+				deprecated: true
+			},
+			OpenapiField {
+				name: "unknown",
+				example: Some(json!(42)),
+				deprecated: true
+			},
+		]);
+
+		let SchemaKind::Type(Type::Object(obj)) = schema.schema.schema_kind else {
+			panic!("Foo should be an object");
+		};
+		let bar = obj.properties["bar"].as_item().unwrap();
+		assert_eq!(bar.schema_data.example, Some(json!("foobar")));
+		assert!(!bar.schema_data.deprecated);
+		let baz = obj.properties["baz"].as_item().unwrap();
+		assert_eq!(baz.schema_data.example, None);
+		assert!(baz.schema_data.deprecated);
+	}
+}
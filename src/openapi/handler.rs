@@ -1,5 +1,5 @@
 #![cfg_attr(not(feature = "auth"), allow(unused_imports))]
-use super::SECURITY_NAME;
+use super::{v3_1, SECURITY_NAME};
 use base64::prelude::*;
 use futures_util::{future, future::FutureExt};
 use gotham::{
@@ -13,7 +13,7 @@ use gotham::{
 		},
 		Body, Response, StatusCode
 	},
-	mime::{APPLICATION_JSON, TEXT_HTML_UTF_8, TEXT_PLAIN_UTF_8},
+	mime::{Mime, APPLICATION_JSON, TEXT_HTML_UTF_8, TEXT_PLAIN_UTF_8},
 	state::State
 };
 use openapi_type::{
@@ -52,6 +52,12 @@ fn get_security(state: &State) -> IndexMap<String, ReferenceOr<SecurityScheme>>
 			bearer_format: Some("JWT".to_owned()),
 			description: None,
 			extensions: Default::default()
+		},
+		AuthSource::QueryParam(name) => SecurityScheme::APIKey {
+			location: APIKeyLocation::Query,
+			name: name.to_owned(),
+			description: None,
+			extensions: Default::default()
 		}
 	};
 
@@ -66,25 +72,194 @@ fn get_security(_state: &State) -> IndexMap<String, ReferenceOr<SecurityScheme>>
 	Default::default()
 }
 
-fn openapi_string(
-	state: &State,
-	openapi: &Arc<RwLock<OpenAPI>>
-) -> Result<String, serde_json::Error> {
-	let openapi = openapi.read();
+#[cfg(feature = "auth")]
+fn add_csrf_header(state: &State, openapi: &mut OpenAPI) {
+	use crate::CsrfConfig;
+	use gotham::{hyper::header::ORIGIN, state::FromState};
+	use openapi_type::{
+		openapiv3::{Parameter, ParameterData, ParameterSchemaOrContent},
+		OpenapiType
+	};
 
+	let (name, description, required) = match CsrfConfig::try_borrow_from(state) {
+		Some(CsrfConfig::DoubleSubmitCookie { cookie, header }) => (
+			header.to_string(),
+			format!("CSRF token, must match the value of the `{cookie}` cookie"),
+			true
+		),
+		Some(CsrfConfig::SameOrigin { trusted_origins }) => {
+			let mut description = "Requests sent by a browser must originate from the same origin \
+				as the server"
+				.to_owned();
+			if !trusted_origins.is_empty() {
+				let origins = trusted_origins
+					.iter()
+					.map(|origin| format!("`{origin}`"))
+					.collect::<Vec<_>>()
+					.join(", ");
+				description = format!("{description} or from one of {origins}");
+			}
+			(ORIGIN.to_string(), description, false)
+		},
+		None => return
+	};
+
+	let param = Parameter::Header {
+		parameter_data: ParameterData {
+			name,
+			description: Some(description),
+			required,
+			deprecated: None,
+			format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(String::schema().schema)),
+			example: None,
+			examples: Default::default(),
+			explode: None,
+			extensions: Default::default()
+		},
+		style: Default::default()
+	};
+
+	for item in openapi.paths.paths.values_mut() {
+		let item = match item {
+			ReferenceOr::Item(item) => item,
+			ReferenceOr::Reference { .. } => continue
+		};
+		for op in [
+			&mut item.put,
+			&mut item.post,
+			&mut item.delete,
+			&mut item.patch
+		]
+		.into_iter()
+		.flatten()
+		{
+			op.parameters.push(ReferenceOr::Item(param.clone()));
+		}
+	}
+}
+
+#[cfg(not(feature = "auth"))]
+fn add_csrf_header(_state: &State, _openapi: &mut OpenAPI) {}
+
+/// Add the parts of the specification that depend on the middlewares in `state`.
+pub(crate) fn prepare_openapi(state: &State, openapi: &OpenAPI) -> OpenAPI {
 	let mut openapi = openapi.clone();
 	let security_schemes = get_security(state);
 	let mut components = openapi.components.unwrap_or_default();
 	components.security_schemes = security_schemes;
 	openapi.components = Some(components);
+	add_csrf_header(state, &mut openapi);
+	openapi
+}
+
+/// `openapiv3` serializes server variables without a description with `"description": null`,
+/// which is not valid OpenAPI.
+fn has_server_variables_without_description(openapi: &OpenAPI) -> bool {
+	openapi
+		.servers
+		.iter()
+		.flat_map(|server| server.variables.iter().flatten())
+		.any(|(_, var)| var.description.is_none())
+}
+
+/// Serialize the specification into a [`Value`](serde_json::Value) if it cannot be serialized
+/// directly, e.g. because it needs to be converted to OpenAPI 3.1.
+fn to_value(openapi: &OpenAPI) -> Option<Result<serde_json::Value, serde_json::Error>> {
+	let is_v3_1 = openapi.openapi == v3_1::OPENAPI_3_1;
+	if !is_v3_1 && !has_server_variables_without_description(openapi) {
+		return None;
+	}
+	Some(serde_json::to_value(openapi).map(|mut value| {
+		let servers = value["servers"].as_array_mut().into_iter().flatten();
+		for server in servers {
+			let vars = server
+				.get_mut("variables")
+				.and_then(|vars| vars.as_object_mut());
+			for var in vars.into_iter().flat_map(|vars| vars.values_mut()) {
+				if var.get("description").is_some_and(|descr| descr.is_null()) {
+					var.as_object_mut().unwrap().remove("description");
+				}
+			}
+		}
+		if is_v3_1 {
+			v3_1::convert(&mut value);
+		}
+		value
+	}))
+}
+
+pub(crate) fn to_json(openapi: &OpenAPI, pretty: bool) -> Result<String, serde_json::Error> {
+	if let Some(openapi) = to_value(openapi) {
+		let openapi = openapi?;
+		return match pretty {
+			true => serde_json::to_string_pretty(&openapi),
+			false => serde_json::to_string(&openapi)
+		};
+	}
+	match pretty {
+		true => serde_json::to_string_pretty(openapi),
+		false => serde_json::to_string(openapi)
+	}
+}
+
+#[cfg(feature = "openapi-yaml")]
+pub(crate) fn to_yaml(openapi: &OpenAPI) -> Result<String, serde_yaml::Error> {
+	if let Some(openapi) = to_value(openapi) {
+		let openapi = openapi.map_err(<serde_yaml::Error as serde::ser::Error>::custom)?;
+		return serde_yaml::to_string(&openapi);
+	}
+	serde_yaml::to_string(openapi)
+}
 
-	serde_json::to_string(&openapi)
+fn openapi_string(
+	state: &State,
+	openapi: &Arc<RwLock<OpenAPI>>
+) -> Result<String, serde_json::Error> {
+	to_json(&prepare_openapi(state, &openapi.read()), false)
 }
 
-fn create_openapi_response(state: &State, openapi: &Arc<RwLock<OpenAPI>>) -> Response<Body> {
-	match openapi_string(state, openapi) {
+#[cfg(feature = "openapi-yaml")]
+fn openapi_yaml_string(
+	state: &State,
+	openapi: &Arc<RwLock<OpenAPI>>
+) -> Result<String, serde_yaml::Error> {
+	to_yaml(&prepare_openapi(state, &openapi.read()))
+}
+
+/// The format of the OpenAPI specification returned by the [OpenapiSpecHandler].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum OpenapiFormat {
+	Json,
+	#[cfg(feature = "openapi-yaml")]
+	Yaml
+}
+
+impl OpenapiFormat {
+	fn render(self, state: &State, openapi: &Arc<RwLock<OpenAPI>>) -> anyhow::Result<String> {
+		Ok(match self {
+			Self::Json => openapi_string(state, openapi)?,
+			#[cfg(feature = "openapi-yaml")]
+			Self::Yaml => openapi_yaml_string(state, openapi)?
+		})
+	}
+
+	fn mime(self) -> Mime {
+		match self {
+			Self::Json => APPLICATION_JSON,
+			#[cfg(feature = "openapi-yaml")]
+			Self::Yaml => "application/yaml".parse().unwrap()
+		}
+	}
+}
+
+fn create_openapi_response(
+	state: &State,
+	openapi: &Arc<RwLock<OpenAPI>>,
+	format: OpenapiFormat
+) -> Response<Body> {
+	match format.render(state, openapi) {
 		Ok(body) => {
-			let mut res = create_response(state, StatusCode::OK, APPLICATION_JSON, body);
+			let mut res = create_response(state, StatusCode::OK, format.mime(), body);
 			let headers = res.headers_mut();
 			headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
 			res
@@ -103,7 +278,8 @@ fn create_openapi_response(state: &State, openapi: &Arc<RwLock<OpenAPI>>) -> Res
 
 #[derive(Clone)]
 pub(crate) struct OpenapiSpecHandler {
-	openapi: Arc<RwLock<OpenAPI>>
+	openapi: Arc<RwLock<OpenAPI>>,
+	format: OpenapiFormat
 }
 
 // safety: the handler only ever aquires a read lock, so this usage of
@@ -111,8 +287,8 @@ pub(crate) struct OpenapiSpecHandler {
 impl RefUnwindSafe for OpenapiSpecHandler {}
 
 impl OpenapiSpecHandler {
-	pub(crate) fn new(openapi: Arc<RwLock<OpenAPI>>) -> Self {
-		Self { openapi }
+	pub(crate) fn new(openapi: Arc<RwLock<OpenAPI>>, format: OpenapiFormat) -> Self {
+		Self { openapi, format }
 	}
 }
 
@@ -126,14 +302,23 @@ impl NewHandler for OpenapiSpecHandler {
 
 impl Handler for OpenapiSpecHandler {
 	fn handle(self, state: State) -> Pin<Box<HandlerFuture>> {
-		let res = create_openapi_response(&state, &self.openapi);
+		let res = create_openapi_response(&state, &self.openapi, self.format);
 		future::ok((state, res)).boxed()
 	}
 }
 
+/// The HTML documentation rendered by the [OpenapiDocHandler].
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum OpenapiDocUi {
+	Redoc,
+	#[cfg(feature = "swagger-ui")]
+	SwaggerUi
+}
+
 #[derive(Clone)]
 pub(crate) struct OpenapiDocHandler {
-	openapi: Arc<RwLock<OpenAPI>>
+	openapi: Arc<RwLock<OpenAPI>>,
+	ui: OpenapiDocUi
 }
 
 // safety: the handler only ever aquires a read lock, so this usage of
@@ -141,8 +326,8 @@ pub(crate) struct OpenapiDocHandler {
 impl RefUnwindSafe for OpenapiDocHandler {}
 
 impl OpenapiDocHandler {
-	pub(crate) fn new(openapi: Arc<RwLock<OpenAPI>>) -> Self {
-		Self { openapi }
+	pub(crate) fn new(openapi: Arc<RwLock<OpenAPI>>, ui: OpenapiDocUi) -> Self {
+		Self { openapi, ui }
 	}
 }
 
@@ -154,15 +339,9 @@ impl NewHandler for OpenapiDocHandler {
 	}
 }
 
-fn redoc_handler(
-	state: &State,
-	openapi: &Arc<RwLock<OpenAPI>>
-) -> Result<Response<Body>, HandlerError> {
-	let spec = openapi_string(state, openapi)?;
-	let redoc = gotham_restful_redoc::html(spec);
-
+fn doc_response(state: &State, html: Vec<u8>, csp: String) -> Response<Body> {
 	let mut etag = Sha256::new();
-	etag.update(&redoc.html);
+	etag.update(&html);
 	let etag = format!("\"{}\"", BASE64_STANDARD.encode(etag.finalize()));
 
 	if state
@@ -170,8 +349,7 @@ fn redoc_handler(
 		.get(IF_NONE_MATCH)
 		.is_some_and(|header| header.as_bytes() == etag.as_bytes())
 	{
-		let res = create_empty_response(state, StatusCode::NOT_MODIFIED);
-		return Ok(res);
+		return create_empty_response(state, StatusCode::NOT_MODIFIED);
 	}
 
 	let mut res = create_empty_response(state, StatusCode::OK);
@@ -181,7 +359,7 @@ fn redoc_handler(
 		CACHE_CONTROL,
 		HeaderValue::from_static("public,max-age=2592000")
 	);
-	headers.insert(CONTENT_SECURITY_POLICY, redoc.csp().parse().unwrap());
+	headers.insert(CONTENT_SECURITY_POLICY, csp.parse().unwrap());
 	headers.insert(ETAG, etag.parse().unwrap());
 	// https://cheatsheetseries.owasp.org/cheatsheets/HTTP_Headers_Cheat_Sheet.html#floc-federated-learning-of-cohorts
 	headers.insert(
@@ -191,13 +369,61 @@ fn redoc_handler(
 	headers.insert(REFERRER_POLICY, HeaderValue::from_static("no-referrer"));
 	headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
 	headers.insert(X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
-	*res.body_mut() = redoc.html.into();
-	Ok(res)
+	*res.body_mut() = html.into();
+	res
+}
+
+fn redoc_handler(
+	state: &State,
+	openapi: &Arc<RwLock<OpenAPI>>
+) -> Result<Response<Body>, HandlerError> {
+	let spec = openapi_string(state, openapi)?;
+	let redoc = gotham_restful_redoc::html(spec);
+	let csp = redoc.csp();
+	Ok(doc_response(state, redoc.html, csp))
+}
+
+/// Substitute the variables of a server url with all of their allowed values.
+#[cfg(feature = "swagger-ui")]
+fn server_urls(server: &openapi_type::openapiv3::Server) -> Vec<String> {
+	let mut urls = vec![server.url.clone()];
+	for (name, var) in server.variables.iter().flatten() {
+		let placeholder = format!("{{{name}}}");
+		let values = match var.enumeration.is_empty() {
+			true => std::slice::from_ref(&var.default),
+			false => var.enumeration.as_slice()
+		};
+		urls = urls
+			.iter()
+			.flat_map(|url| values.iter().map(|value| url.replace(&placeholder, value)))
+			.collect();
+	}
+	urls
+}
+
+#[cfg(feature = "swagger-ui")]
+fn swagger_ui_handler(
+	state: &State,
+	openapi: &Arc<RwLock<OpenAPI>>
+) -> Result<Response<Body>, HandlerError> {
+	let spec = openapi_string(state, openapi)?;
+	let swagger_ui = {
+		let openapi = openapi.read();
+		let servers: Vec<String> = openapi.servers.iter().flat_map(server_urls).collect();
+		gotham_restful_swagger_ui::html(spec, servers.iter().map(String::as_str))
+	};
+	let csp = swagger_ui.csp();
+	Ok(doc_response(state, swagger_ui.html, csp))
 }
 
 impl Handler for OpenapiDocHandler {
 	fn handle(self, state: State) -> Pin<Box<HandlerFuture>> {
-		match redoc_handler(&state, &self.openapi) {
+		let res = match self.ui {
+			OpenapiDocUi::Redoc => redoc_handler(&state, &self.openapi),
+			#[cfg(feature = "swagger-ui")]
+			OpenapiDocUi::SwaggerUi => swagger_ui_handler(&state, &self.openapi)
+		};
+		match res {
 			Ok(res) => future::ok((state, res)).boxed(),
 			Err(err) => future::err((state, err)).boxed()
 		}
@@ -1,6 +1,17 @@
+//! Tools to work with generated OpenAPI specifications, like detecting breaking changes.
+
 const SECURITY_NAME: &str = "authToken";
 
 pub(crate) mod builder;
+mod diff;
+pub(crate) mod fields;
 pub(crate) mod handler;
 pub(crate) mod operation;
+#[cfg(feature = "validate-responses")]
+pub(crate) mod response_validation;
 pub(crate) mod router;
+pub(crate) mod spec;
+pub(crate) mod v3_1;
+pub(crate) mod validation;
+
+pub use diff::{assert_no_breaking_changes, diff, Change, ChangeKind, OpenapiDiff};
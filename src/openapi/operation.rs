@@ -1,12 +1,15 @@
-use super::SECURITY_NAME;
+use super::{builder::OpenapiBuilder, SECURITY_NAME};
 use crate::{response::OrAllTypes, EndpointWithSchema, IntoResponse, RequestBody};
-use gotham::{hyper::StatusCode, mime::Mime};
+use gotham::{
+	hyper::{Method, StatusCode},
+	mime::Mime
+};
 use openapi_type::{
 	indexmap::IndexMap,
 	openapiv3::{
-		MediaType, Operation, Parameter, ParameterData, ParameterSchemaOrContent, ReferenceOr,
-		ReferenceOr::Item, RequestBody as OARequestBody, Response, Responses, Schema, SchemaKind,
-		StatusCode as OAStatusCode, Type
+		Header, MediaType, Operation, Parameter, ParameterData, ParameterSchemaOrContent,
+		ReferenceOr, ReferenceOr::Item, RequestBody as OARequestBody, Response, Responses, Schema,
+		SchemaKind, StatusCode as OAStatusCode, Type
 	},
 	OpenapiSchema
 };
@@ -15,21 +18,52 @@ use std::{borrow::Cow, collections::HashMap};
 fn new_parameter_data(
 	name: String,
 	required: bool,
-	schema: ReferenceOr<Box<Schema>>
+	mut schema: ReferenceOr<Box<Schema>>
 ) -> ParameterData {
+	// the documentation of the field belongs to the parameter, not its schema
+	let (description, deprecated, example) = match &mut schema {
+		Item(schema) => {
+			let data = &mut schema.schema_data;
+			let deprecated = std::mem::take(&mut data.deprecated);
+			(
+				data.description.take(),
+				deprecated.then_some(true),
+				data.example.take()
+			)
+		},
+		_ => (None, None, None)
+	};
+
 	ParameterData {
 		name,
-		description: None,
+		description,
 		required,
-		deprecated: None,
+		deprecated,
 		format: ParameterSchemaOrContent::Schema(schema.unbox()),
-		example: None,
+		example,
 		examples: Default::default(),
 		explode: None,
 		extensions: Default::default()
 	}
 }
 
+pub(crate) fn new_header_data(
+	description: Option<String>,
+	required: bool,
+	schema: ReferenceOr<Schema>
+) -> Header {
+	Header {
+		description,
+		style: Default::default(),
+		required,
+		deprecated: None,
+		format: ParameterSchemaOrContent::Schema(schema),
+		example: None,
+		examples: Default::default(),
+		extensions: Default::default()
+	}
+}
+
 #[derive(Default)]
 struct OperationParams {
 	path_params: Option<OpenapiSchema>,
@@ -104,12 +138,143 @@ pub enum OperationId {
 	Manual(String)
 }
 
+/// The naming convention of automatically generated operation ids. Operation ids that were
+/// provided using [OperationId::Manual] are used as is.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OperationIdStyle {
+	/// Generate operation ids from the verb of the endpoint and the path of the resource with
+	/// all `/` replaced by `_`, like `read_all_foo`. Placeholders are kept, and endpoints without
+	/// a verb don't get an operation id.
+	#[default]
+	Legacy,
+	/// Generate valid identifiers like `read_all_foo`, leaving out placeholders.
+	SnakeCase,
+	/// Generate valid identifiers like `readAllFoo`, leaving out placeholders.
+	CamelCase
+}
+
+impl OperationIdStyle {
+	/// The separator between an operation id and the number that makes it unique.
+	fn separator(self) -> &'static str {
+		match self {
+			Self::Legacy | Self::SnakeCase => "_",
+			Self::CamelCase => ""
+		}
+	}
+
+	/// Join `words` into a valid identifier in this style.
+	fn join(self, words: &[String]) -> String {
+		let id = match self {
+			Self::Legacy | Self::SnakeCase => words.join("_"),
+			Self::CamelCase => words
+				.iter()
+				.enumerate()
+				.map(|(i, word)| match i {
+					0 => word.clone(),
+					_ => word[..1].to_ascii_uppercase() + &word[1..]
+				})
+				.collect()
+		};
+		match id.chars().next() {
+			Some(ch) if ch.is_ascii_alphabetic() => id,
+			_ => format!("op{}{id}", self.separator())
+		}
+	}
+}
+
+/// Split `str` into lower case words. Words are separated by any character that is not
+/// alphanumeric, or by an upper case letter.
+fn split_words(str: &str) -> Vec<String> {
+	let mut words: Vec<String> = Vec::new();
+	let mut prev_is_upper = true;
+	for ch in str.chars() {
+		if !ch.is_ascii_alphanumeric() {
+			words.push(String::new());
+			prev_is_upper = true;
+			continue;
+		}
+		if words.is_empty() || (ch.is_ascii_uppercase() && !prev_is_upper) {
+			words.push(String::new());
+		}
+		prev_is_upper = ch.is_ascii_uppercase();
+		words.last_mut().unwrap().push(ch.to_ascii_lowercase());
+	}
+	words.retain(|word| !word.is_empty());
+	words
+}
+
+/// Generate an operation id from the verb of the endpoint and its path, like previous versions
+/// of gotham_restful did.
+fn legacy_operation_id(verb: &str, path: &str) -> String {
+	let op_path = path.replace('/', "_");
+	let op_path = op_path.trim_start_matches('_');
+	if verb.starts_with(op_path) || verb.ends_with(op_path) {
+		verb.to_owned()
+	} else {
+		format!("{verb}_{op_path}")
+	}
+}
+
+/// Generate an operation id from the verb of the endpoint and its path, leaving out any
+/// placeholders.
+fn generate_operation_id(verb: &str, path: &str, style: OperationIdStyle) -> String {
+	let mut words = split_words(verb);
+	let path_words = path
+		.split('/')
+		.filter(|segment| !segment.starts_with([':', '{', '*']))
+		.flat_map(split_words)
+		.collect::<Vec<_>>();
+	if !words.starts_with(&path_words) && !words.ends_with(&path_words) {
+		words.extend(path_words);
+	}
+	style.join(&words)
+}
+
+/// Split a doc comment into the summary, which is its first paragraph, and the remaining
+/// description.
+fn split_summary(description: &str) -> (String, Option<String>) {
+	match description.split_once("\n\n") {
+		Some((summary, description)) => (
+			summary.replace('\n', " "),
+			Some(description.trim_start().to_owned())
+		),
+		None => (description.replace('\n', " "), None)
+	}
+}
+
+/// A response of an operation for one status code.
+pub(crate) struct OperationResponse {
+	pub(crate) schema: ReferenceOr<Schema>,
+	pub(crate) description: Option<String>,
+	pub(crate) headers: IndexMap<String, ReferenceOr<Header>>,
+	/// The content types of this response, if they differ from the endpoint's accepted types.
+	pub(crate) content_types: Option<Vec<Mime>>
+}
+
+impl OperationResponse {
+	/// A response that is emitted by gotham_restful itself rather than the endpoint.
+	pub(crate) fn framework(
+		schema: ReferenceOr<Schema>,
+		content_types: Vec<Mime>,
+		description: &str
+	) -> Self {
+		Self {
+			schema,
+			description: Some(description.to_owned()),
+			headers: IndexMap::new(),
+			content_types: Some(content_types)
+		}
+	}
+}
+
 pub(crate) struct OperationDescription {
 	operation_id: Option<String>,
 	description: Option<String>,
+	tags: Vec<String>,
+	deprecated: bool,
 
 	accepted_types: Option<Vec<Mime>>,
-	responses: HashMap<StatusCode, ReferenceOr<Schema>>,
+	responses: HashMap<StatusCode, OperationResponse>,
 	params: OperationParams,
 	body_schema: Option<ReferenceOr<Schema>>,
 	supported_types: Option<Vec<Mime>>,
@@ -118,29 +283,37 @@ pub(crate) struct OperationDescription {
 
 impl OperationDescription {
 	/// Create a new operation description for the given endpoint type and schema. If the endpoint
-	/// does not specify an operation id, the path is used to generate one.
+	/// does not specify an operation id, the resource path is used to generate one in `style`.
 	pub(crate) fn new<E: EndpointWithSchema>(
-		responses: HashMap<StatusCode, ReferenceOr<Schema>>,
-		path: &str
+		responses: HashMap<StatusCode, OperationResponse>,
+		path: &str,
+		style: OperationIdStyle
 	) -> Self {
-		let (mut operation_id, op_id_verb) = match E::operation_id() {
-			OperationId::FullAuto => (None, E::operation_verb().map(Cow::Borrowed)),
-			OperationId::SemiAuto(verb) => (None, Some(verb)),
-			OperationId::Manual(id) => (Some(id), None)
+		let operation_id = match (E::operation_id(), style) {
+			(OperationId::Manual(id), _) => Some(id),
+			(OperationId::SemiAuto(verb), OperationIdStyle::Legacy) => {
+				Some(legacy_operation_id(&verb, path))
+			},
+			(OperationId::FullAuto, OperationIdStyle::Legacy) => {
+				E::operation_verb().map(|verb| legacy_operation_id(verb, path))
+			},
+			(OperationId::SemiAuto(verb), style) => Some(generate_operation_id(&verb, path, style)),
+			(OperationId::FullAuto, style) => Some(match E::operation_verb() {
+				Some(verb) => generate_operation_id(verb, path, style),
+				// without a verb, use the http method and the full path of the endpoint
+				None => {
+					let method = E::http_method().as_str().to_owned();
+					let path = format!("{path}/{}", E::uri());
+					generate_operation_id(&method, &path, style)
+				}
+			})
 		};
-		if let Some(verb) = op_id_verb {
-			let op_path = path.replace('/', "_");
-			let op_path = op_path.trim_start_matches('_');
-			if verb.starts_with(op_path) || verb.ends_with(op_path) {
-				operation_id = Some(verb.into_owned());
-			} else {
-				operation_id = Some(format!("{verb}_{op_path}"));
-			}
-		}
 
 		Self {
 			operation_id,
 			description: E::description(),
+			tags: E::tags().into_iter().map(Cow::into_owned).collect(),
+			deprecated: E::deprecated(),
 
 			accepted_types: E::Output::accepted_types(),
 			responses,
@@ -151,6 +324,40 @@ impl OperationDescription {
 		}
 	}
 
+	/// Make sure that no other operation than `method` at `path` uses the same operation id by
+	/// appending a number if necessary.
+	pub(crate) fn make_operation_id_unique(
+		&mut self,
+		builder: &OpenapiBuilder,
+		path: &str,
+		method: &Method
+	) {
+		let Some(operation_id) = &self.operation_id else {
+			return;
+		};
+		let used = builder.operation_ids(path, method);
+		let Some(other) = used.get(operation_id) else {
+			return;
+		};
+
+		let separator = builder.operation_id_style.separator();
+		let unique = (2..)
+			.map(|i| format!("{operation_id}{separator}{i}"))
+			.find(|id| !used.contains_key(id))
+			.unwrap();
+		warn!(
+			"The operation id {operation_id:?} of {method} {path} is already used by {other}, using {unique:?} instead"
+		);
+		self.operation_id = Some(unique);
+	}
+
+	/// Set the tag of the resource, unless the endpoint overrides it.
+	pub(crate) fn set_default_tag(&mut self, tag: &str) {
+		if self.tags.is_empty() {
+			self.tags.push(tag.to_owned());
+		}
+	}
+
 	pub(crate) fn set_path_params(&mut self, params: OpenapiSchema) {
 		self.params.path_params = Some(params);
 	}
@@ -183,6 +390,8 @@ impl OperationDescription {
 		let (
 			operation_id,
 			description,
+			tags,
+			deprecated,
 			accepted_types,
 			responses,
 			params,
@@ -192,6 +401,8 @@ impl OperationDescription {
 		) = (
 			self.operation_id,
 			self.description,
+			self.tags,
+			self.deprecated,
 			self.accepted_types,
 			self.responses,
 			self.params,
@@ -202,16 +413,21 @@ impl OperationDescription {
 
 		let responses: IndexMap<OAStatusCode, ReferenceOr<Response>> = responses
 			.into_iter()
-			.map(|(code, schema)| {
-				let content =
-					Self::schema_to_content(accepted_types.clone().or_all_types(), schema);
+			.map(|(code, response)| {
+				let content_types = response
+					.content_types
+					.unwrap_or_else(|| accepted_types.clone().or_all_types());
+				let content = Self::schema_to_content(content_types, response.schema);
+				let description = response.description.unwrap_or_else(|| {
+					code.canonical_reason()
+						.map(|d| d.to_string())
+						.unwrap_or_default()
+				});
 				(
 					OAStatusCode::Code(code.as_u16()),
 					Item(Response {
-						description: code
-							.canonical_reason()
-							.map(|d| d.to_string())
-							.unwrap_or_default(),
+						description,
+						headers: response.headers,
 						content,
 						..Default::default()
 					})
@@ -234,8 +450,17 @@ impl OperationDescription {
 			security = Some(vec![sec]);
 		}
 
+		let (summary, description) = match description {
+			Some(description) => {
+				let (summary, description) = split_summary(&description);
+				(Some(summary), description)
+			},
+			None => (None, None)
+		};
+
 		Operation {
-			tags: Vec::new(),
+			tags,
+			summary,
 			operation_id,
 			description,
 			parameters: params.into_params(),
@@ -244,7 +469,7 @@ impl OperationDescription {
 				responses,
 				..Default::default()
 			},
-			deprecated: false,
+			deprecated,
 			security,
 			..Default::default()
 		}
@@ -255,6 +480,7 @@ impl OperationDescription {
 mod test {
 	use super::*;
 	use crate::{NoContent, Raw, ResponseSchema};
+	use openapi_type::OpenapiType;
 
 	#[test]
 	fn no_content_schema_to_content() {
@@ -275,4 +501,70 @@ mod test {
 		let json = serde_json::to_string(&content.values().next().unwrap()).unwrap();
 		assert_eq!(json, r#"{"schema":{"type":"string","format":"binary"}}"#);
 	}
+
+	#[test]
+	fn legacy_operation_ids() {
+		assert_eq!(legacy_operation_id("read_all", "/foo"), "read_all_foo");
+		assert_eq!(legacy_operation_id("read", "/foo/bar"), "read_foo_bar");
+		assert_eq!(legacy_operation_id("foo_read", "/foo"), "foo_read");
+	}
+
+	#[test]
+	fn generate_operation_ids() {
+		let snake = OperationIdStyle::SnakeCase;
+		let camel = OperationIdStyle::CamelCase;
+		assert_eq!(
+			generate_operation_id("read_all", "/foo", snake),
+			"read_all_foo"
+		);
+		assert_eq!(
+			generate_operation_id("read_all", "/foo", camel),
+			"readAllFoo"
+		);
+		assert_eq!(generate_operation_id("read", "", snake), "read");
+		assert_eq!(
+			generate_operation_id("search", "/users/:user_id/my-posts", snake),
+			"search_users_my_posts"
+		);
+		assert_eq!(
+			generate_operation_id("read", "/{version}/fooBar", snake),
+			"read_foo_bar"
+		);
+		assert_eq!(generate_operation_id("foo_read", "/foo", snake), "foo_read");
+		assert_eq!(generate_operation_id("read_foo", "foo", camel), "readFoo");
+		assert_eq!(generate_operation_id("", "/2fa", snake), "op_2fa");
+		assert_eq!(generate_operation_id("", "/2fa", camel), "op2fa");
+	}
+
+	#[test]
+	fn parameter_data_from_field_schema() {
+		let mut schema = <String as OpenapiType>::schema().schema;
+		schema.schema_data.description = Some("The name of the user.".to_owned());
+		schema.schema_data.deprecated = true;
+		schema.schema_data.example = Some("alice".into());
+
+		let data = new_parameter_data("name".to_owned(), true, Item(Box::new(schema)));
+		assert_eq!(data.description.as_deref(), Some("The name of the user."));
+		assert_eq!(data.deprecated, Some(true));
+		assert_eq!(data.example, Some("alice".into()));
+		let ParameterSchemaOrContent::Schema(Item(schema)) = data.format else {
+			panic!("Expected an inline schema");
+		};
+		assert_eq!(schema.schema_data, Default::default());
+	}
+
+	#[test]
+	fn split_summary_from_description() {
+		assert_eq!(
+			split_summary("Get a user."),
+			("Get a user.".to_owned(), None)
+		);
+		assert_eq!(
+			split_summary("Get a user\nby id.\n\nRequires authentication.\n\nReturns 404."),
+			(
+				"Get a user by id.".to_owned(),
+				Some("Requires authentication.\n\nReturns 404.".to_owned())
+			)
+		);
+	}
 }
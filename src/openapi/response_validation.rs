@@ -0,0 +1,195 @@
+use super::validation::{is_json, Schemas};
+use crate::{EndpointWithSchema, Response, ResponseSchema};
+use gotham::{
+	hyper::{body::to_bytes, Body, StatusCode},
+	mime::Mime,
+	prelude::StateData,
+	state::{FromState, State}
+};
+use serde_json::Value;
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+/// What to do when a response does not match the schema of its endpoint.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ResponseValidationMode {
+	/// Log the violations as errors and send the response anyways.
+	#[default]
+	Log,
+	/// Panic with a message that contains all violations. Gotham answers the request with
+	/// _500 Internal Server Error_ instead, which makes tests fail that expect a successful
+	/// response.
+	Panic
+}
+
+/// Validates the responses of an endpoint against the schemas it declares.
+#[derive(Debug)]
+pub(crate) struct ResponseValidator {
+	/// The method and path of the endpoint, used in the reported violations.
+	endpoint: String,
+	mode: ResponseValidationMode,
+	schemas: Schemas,
+	/// The schema for each status code returned by `ResponseSchema::status_codes`.
+	responses: HashMap<StatusCode, Value>
+}
+
+/// The [ResponseValidator] of the current endpoint, if response validation is enabled.
+#[derive(Clone, Debug, StateData)]
+pub(crate) struct ResponseValidation(pub(crate) Arc<ResponseValidator>);
+
+impl ResponseValidator {
+	/// Create a validator for the endpoint `E`, where `endpoint` is its method and path.
+	pub(crate) fn new<E: EndpointWithSchema>(
+		endpoint: String,
+		mode: ResponseValidationMode
+	) -> Self {
+		let mut schemas = Schemas::default();
+		let responses = E::Output::status_codes()
+			.into_iter()
+			.map(|code| (code, schemas.add_schema(E::Output::schema(code))))
+			.collect::<HashMap<_, _>>();
+		schemas.compile_patterns(responses.values());
+		Self {
+			endpoint,
+			mode,
+			schemas,
+			responses
+		}
+	}
+
+	/// Validate a response with `status`, `mime` and `body`. Returns all violations.
+	fn validate(&self, status: StatusCode, mime: Option<&Mime>, body: &[u8]) -> Vec<String> {
+		let Some(schema) = self.responses.get(&status) else {
+			let mut codes = self
+				.responses
+				.keys()
+				.map(|code| code.as_u16())
+				.collect::<Vec<_>>();
+			codes.sort_unstable();
+			return vec![format!(
+				"status code {} is not one of the declared status codes {codes:?}",
+				status.as_u16()
+			)];
+		};
+		if !mime.is_some_and(is_json) {
+			return Vec::new();
+		}
+		match serde_json::from_slice::<Value>(body) {
+			Ok(body) => self
+				.schemas
+				.validate("body", schema, &body)
+				.into_iter()
+				.map(|err| format!("{:?}: {}", err.pointer, err.message))
+				.collect(),
+			Err(err) => vec![format!("body is not valid json: {err}")]
+		}
+	}
+
+	fn report(&self, violations: &[String]) {
+		let msg = format!(
+			"The response of {} does not match its schema: {}",
+			self.endpoint,
+			violations.join(", ")
+		);
+		match self.mode {
+			ResponseValidationMode::Log => error!("{msg}"),
+			ResponseValidationMode::Panic => panic!("{msg}")
+		}
+	}
+}
+
+/// Validate the response `res` if response validation is enabled for the endpoint. The body of
+/// the response is buffered to validate it.
+pub(crate) fn validate_response(
+	state: &State,
+	mut res: Response
+) -> impl Future<Output = Result<Response, gotham::hyper::Error>> + Send {
+	// the state must not be borrowed across the await point
+	let validation = ResponseValidation::try_borrow_from(state).cloned();
+	async move {
+		let Some(ResponseValidation(validator)) = validation else {
+			return Ok(res);
+		};
+		let body = to_bytes(std::mem::take(&mut res.body)).await?;
+		let violations = validator.validate(res.status, res.mime.as_ref(), &body);
+		if !violations.is_empty() {
+			validator.report(&violations);
+		}
+		res.body = Body::from(body);
+		Ok(res)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::Success;
+	use futures_util::future::{self, BoxFuture, FutureExt};
+	use gotham::{
+		hyper::Method,
+		mime::{APPLICATION_JSON, TEXT_PLAIN}
+	};
+	use std::borrow::Cow;
+
+	struct Endpoint;
+
+	impl EndpointWithSchema for Endpoint {
+		fn http_method() -> Method {
+			Method::GET
+		}
+		fn uri() -> Cow<'static, str> {
+			"".into()
+		}
+		fn operation_verb() -> Option<&'static str> {
+			None
+		}
+		type Output = Success<Vec<u32>>;
+		type Placeholders = crate::NoopExtractor;
+		type Params = crate::NoopExtractor;
+		type Body = ();
+		fn handle(
+			_: &mut State,
+			_: Self::Placeholders,
+			_: Self::Params,
+			_: Option<()>
+		) -> BoxFuture<'_, Self::Output> {
+			future::ready(vec![1, 2].into()).boxed()
+		}
+	}
+
+	fn validator() -> ResponseValidator {
+		ResponseValidator::new::<Endpoint>("GET /".to_owned(), ResponseValidationMode::Log)
+	}
+
+	#[test]
+	fn validate_body() {
+		let validator = validator();
+		let json = Some(&APPLICATION_JSON);
+		assert!(validator
+			.validate(StatusCode::OK, json, b"[1, 2]")
+			.is_empty());
+		assert_eq!(
+			validator.validate(StatusCode::OK, json, br#"[1, "2"]"#),
+			vec![r#""/1": must be an integer"#.to_owned()]
+		);
+		assert_eq!(validator.validate(StatusCode::OK, json, b"[").len(), 1);
+		assert!(validator
+			.validate(StatusCode::OK, Some(&TEXT_PLAIN), b"foo")
+			.is_empty());
+	}
+
+	#[test]
+	fn validate_status_code() {
+		let validator = validator();
+		assert_eq!(validator.validate(StatusCode::NO_CONTENT, None, b""), vec![
+			"status code 204 is not one of the declared status codes [200]".to_owned()
+		]);
+	}
+
+	#[test]
+	#[should_panic(expected = "The response of GET / does not match its schema")]
+	fn panic_mode() {
+		let validator =
+			ResponseValidator::new::<Endpoint>("GET /".to_owned(), ResponseValidationMode::Panic);
+		validator.report(&["status code 201 is not declared".to_owned()]);
+	}
+}
@@ -1,26 +1,48 @@
+#[cfg(feature = "validate-responses")]
+use super::response_validation::{ResponseValidation, ResponseValidationMode, ResponseValidator};
 use super::{
-	builder::OpenapiBuilder,
-	handler::{OpenapiDocHandler, OpenapiSpecHandler},
-	operation::OperationDescription
+	builder::{
+		OpenapiBuilder, OpenapiContact, OpenapiExternalDocs, OpenapiInfo, OpenapiLicense,
+		OpenapiServer, OpenapiVersion
+	},
+	fields::add_fields,
+	handler::{OpenapiDocHandler, OpenapiDocUi, OpenapiFormat, OpenapiSpecHandler},
+	operation::{new_header_data, OperationDescription, OperationIdStyle, OperationResponse},
+	validation::{RequestValidation, RequestValidator, ValidationErrors}
+};
+use crate::{
+	response::ResourceError, routing::*, EndpointWithSchema, IntoResponse, NoContent,
+	ResourceWithSchema, ResponseSchema
 };
-use crate::{routing::*, EndpointWithSchema, ResourceWithSchema, ResponseSchema};
 use gotham::{
-	hyper::{Method, StatusCode},
+	hyper::StatusCode,
+	mime::APPLICATION_JSON,
 	pipeline::PipelineHandleChain,
 	prelude::*,
 	router::builder::{RouterBuilder, ScopeBuilder}
 };
 use lazy_regex::regex_replace_all;
-use openapi_type::OpenapiType;
-use std::{collections::HashMap, panic::RefUnwindSafe};
+use openapi_type::{openapiv3::ReferenceOr::Item, OpenapiType};
+use std::{collections::HashMap, panic::RefUnwindSafe, sync::Arc};
 
 /// This trait adds the `openapi_spec` and `openapi_doc` method to an OpenAPI-aware router.
 pub trait GetOpenapi {
 	/// Register a GET route to `path` that returns the OpenAPI specification in JSON format.
 	fn openapi_spec(&mut self, path: &str);
 
+	/// Register a GET route to `path` that returns the OpenAPI specification in YAML format. This
+	/// method requires the `openapi-yaml` feature.
+	#[cfg(feature = "openapi-yaml")]
+	fn openapi_spec_yaml(&mut self, path: &str);
+
 	/// Register a GET route to `path` that returns the OpenAPI documentation in HTML format.
 	fn openapi_doc(&mut self, path: &str);
+
+	/// Register a GET route to `path` that returns the OpenAPI documentation rendered by
+	/// Swagger UI, which allows to try out the endpoints. This method requires the `swagger-ui`
+	/// feature.
+	#[cfg(feature = "swagger-ui")]
+	fn openapi_swagger_ui(&mut self, path: &str);
 }
 
 #[derive(Debug)]
@@ -30,6 +52,111 @@ pub struct OpenapiRouter<'a, D> {
 	pub(crate) openapi_builder: &'a mut OpenapiBuilder
 }
 
+impl<D> OpenapiRouter<'_, D> {
+	/// Validate the path and query parameters and the json request body of all endpoints that
+	/// are registered afterwards against their schema, including in nested scopes. Requests that
+	/// violate the schema are answered with _422 Unprocessable Entity_ and a list of the
+	/// violations, without calling the endpoint.
+	///
+	/// The `pattern` of string schemas is matched using the [regex](https://docs.rs/regex) crate
+	/// instead of ECMA-262 regular expressions, so patterns using lookarounds or backreferences
+	/// are ignored with a warning.
+	pub fn validate_requests(&mut self) {
+		self.openapi_builder.validate_requests = true;
+	}
+
+	/// Validate the status code and the json body of every response of all endpoints that are
+	/// registered afterwards against the schemas declared by their [ResponseSchema]
+	/// implementation, including in nested scopes. Violations are reported according to `mode`.
+	/// This buffers all response bodies and is intended for debugging and tests. This method
+	/// requires the `validate-responses` feature.
+	#[cfg(feature = "validate-responses")]
+	pub fn validate_responses(&mut self, mode: ResponseValidationMode) {
+		self.openapi_builder.validate_responses = Some(mode);
+	}
+
+	/// Generate the operation ids of all endpoints that are registered afterwards in `style`,
+	/// including in nested scopes. The default is [OperationIdStyle::Legacy].
+	pub fn operation_id_style(&mut self, style: OperationIdStyle) {
+		self.openapi_builder.operation_id_style = style;
+	}
+
+	/// Emit version `version` of the OpenAPI specification for the current document, i.e. the
+	/// document passed to `with_openapi` or the first document selected by
+	/// [in_documents](Self::in_documents). The default is [OpenapiVersion::V3_0].
+	pub fn openapi_version(&mut self, version: OpenapiVersion) {
+		self.openapi_builder.openapi.write().openapi = version.as_str().to_owned();
+	}
+
+	/// Set the description of the current document. CommonMark syntax may be used.
+	pub fn description(&mut self, description: &str) {
+		self.openapi_builder.openapi.write().info.description = Some(description.to_owned());
+	}
+
+	/// Set the url to the terms of service of the current document.
+	pub fn terms_of_service(&mut self, url: &str) {
+		self.openapi_builder.openapi.write().info.terms_of_service = Some(url.to_owned());
+	}
+
+	/// Set the contact information of the current document.
+	pub fn contact(&mut self, contact: OpenapiContact) {
+		self.openapi_builder.openapi.write().info.contact = Some(contact.into());
+	}
+
+	/// Set the license of the current document.
+	pub fn license(&mut self, license: OpenapiLicense) {
+		self.openapi_builder.openapi.write().info.license = Some(license.into());
+	}
+
+	/// Link the current document to additional external documentation.
+	pub fn external_docs(&mut self, docs: OpenapiExternalDocs) {
+		self.openapi_builder.openapi.write().external_docs = Some(docs.into());
+	}
+
+	/// Add a server with a description or a templated url to the current document, after the
+	/// servers from the [urls](OpenapiInfo::urls) and any server added before.
+	pub fn add_server(&mut self, server: OpenapiServer) {
+		self.openapi_builder
+			.openapi
+			.write()
+			.servers
+			.push(server.into());
+	}
+
+	/// Create an additional OpenAPI document called `name`. Endpoints are only added to it if
+	/// they are registered inside [in_documents](Self::in_documents). The operation id style and
+	/// the validation settings of the router also apply to this document.
+	///
+	/// # Panics
+	///
+	/// This method panics if a document with the same name already exists.
+	pub fn add_document(&mut self, name: &str, info: OpenapiInfo) {
+		self.openapi_builder.add_document(name, info);
+	}
+
+	/// Add all endpoints that are registered inside `callback` to the documents called `names`
+	/// instead of the current ones. The document passed to `with_openapi` is called
+	/// [DEFAULT_OPENAPI_DOCUMENT](crate::DEFAULT_OPENAPI_DOCUMENT), and additional documents are
+	/// created using [add_document](Self::add_document). The specification handlers registered
+	/// inside `callback` serve the first of these documents.
+	///
+	/// # Panics
+	///
+	/// This method panics if `names` is empty or contains an unknown document name.
+	pub fn in_documents<F>(&mut self, names: &[&str], callback: F)
+	where
+		F: FnOnce(&mut OpenapiRouter<'_, D>)
+	{
+		let mut openapi_builder = self.openapi_builder.select_documents(names);
+		let mut router = OpenapiRouter {
+			router: &mut *self.router,
+			scope: self.scope,
+			openapi_builder: &mut openapi_builder
+		};
+		callback(&mut router);
+	}
+}
+
 macro_rules! implOpenapiRouter {
 	($implType:ident) => {
 		impl<'a, 'b, C, P> OpenapiRouter<'a, $implType<'b, C, P>>
@@ -65,14 +192,34 @@ macro_rules! implOpenapiRouter {
 				self.router
 					.get(path)
 					.to_new_handler(OpenapiSpecHandler::new(
-						self.openapi_builder.openapi.clone()
+						self.openapi_builder.openapi.clone(),
+						OpenapiFormat::Json
 					));
 			}
 
-			fn openapi_doc(&mut self, path: &str) {
+			#[cfg(feature = "openapi-yaml")]
+			fn openapi_spec_yaml(&mut self, path: &str) {
 				self.router
 					.get(path)
-					.to_new_handler(OpenapiDocHandler::new(self.openapi_builder.openapi.clone()));
+					.to_new_handler(OpenapiSpecHandler::new(
+						self.openapi_builder.openapi.clone(),
+						OpenapiFormat::Yaml
+					));
+			}
+
+			fn openapi_doc(&mut self, path: &str) {
+				self.router.get(path).to_new_handler(OpenapiDocHandler::new(
+					self.openapi_builder.openapi.clone(),
+					OpenapiDocUi::Redoc
+				));
+			}
+
+			#[cfg(feature = "swagger-ui")]
+			fn openapi_swagger_ui(&mut self, path: &str) {
+				self.router.get(path).to_new_handler(OpenapiDocHandler::new(
+					self.openapi_builder.openapi.clone(),
+					OpenapiDocUi::SwaggerUi
+				));
 			}
 		}
 
@@ -85,34 +232,152 @@ macro_rules! implOpenapiRouter {
 				if path.starts_with('/') {
 					path = &path[1..];
 				}
-				R::setup((self, path));
+				R::setup((self, path, &mut ResourceContext::default()));
 			}
 		}
 
 		impl<'a, 'b, C, P> DrawResourceRoutesWithSchema
-			for (&mut OpenapiRouter<'a, $implType<'b, C, P>>, &str)
+			for (
+				&mut OpenapiRouter<'a, $implType<'b, C, P>>,
+				&str,
+				&mut ResourceContext
+			)
 		where
 			C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
 			P: RefUnwindSafe + Send + Sync + 'static
 		{
 			fn endpoint<E: EndpointWithSchema + 'static>(&mut self) {
+				// hidden endpoints are added to a detached document, so that they can still be
+				// validated against their schema
+				let mut detached;
+				let builder = match E::hidden() {
+					true => {
+						detached = (self.0).openapi_builder.detached();
+						&mut detached
+					},
+					false => &mut *(self.0).openapi_builder
+				};
+
 				let mut responses: HashMap<StatusCode, _> = HashMap::new();
 				for code in E::Output::status_codes() {
+					let headers = E::Output::headers(code)
+						.into_iter()
+						.chain(E::headers(code))
+						.map(|header| {
+							let schema = builder.add_schema(header.schema);
+							let header_data =
+								new_header_data(header.description, header.required, schema);
+							(header.name.to_string(), Item(header_data))
+						})
+						.collect();
+					responses.insert(code, OperationResponse {
+						schema: builder.add_schema(E::Output::schema(code)),
+						description: E::Output::description(code),
+						headers,
+						content_types: None
+					});
+				}
+
+				// responses that are emitted before the endpoint is called, unless the endpoint
+				// documents these status codes itself
+				let no_content = builder.add_schema(<NoContent as ResponseSchema>::schema(
+					StatusCode::NO_CONTENT
+				));
+				let needs_extraction = E::has_placeholders() || E::needs_params();
+				if E::needs_body() {
+					if !responses.contains_key(&StatusCode::UNSUPPORTED_MEDIA_TYPE) {
+						responses.insert(
+							StatusCode::UNSUPPORTED_MEDIA_TYPE,
+							OperationResponse::framework(
+								no_content.clone(),
+								Vec::new(),
+								"The content type of the request body is not supported."
+							)
+						);
+					}
+					if !responses.contains_key(&StatusCode::BAD_REQUEST) {
+						// gotham answers invalid path or query parameters with an empty body
+						let description = match needs_extraction {
+							true => {
+								"The request body, path or query parameters could not be parsed."
+							},
+							false => "The request body could not be parsed."
+						};
+						responses.insert(
+							StatusCode::BAD_REQUEST,
+							OperationResponse::framework(
+								builder.add_schema(ResourceError::schema()),
+								vec![APPLICATION_JSON],
+								description
+							)
+						);
+					}
+				}
+				if needs_extraction && !responses.contains_key(&StatusCode::BAD_REQUEST) {
+					responses.insert(
+						StatusCode::BAD_REQUEST,
+						OperationResponse::framework(
+							no_content.clone(),
+							Vec::new(),
+							"The path or query parameters could not be parsed."
+						)
+					);
+				}
+				let validator = builder
+					.validate_requests
+					.then(|| {
+						let scope = self.0.scope.unwrap_or_default();
+						RequestValidator::new::<E>(&format!("{scope}/{}/{}", self.1, E::uri()))
+					})
+					.flatten();
+				if validator.is_some() && !responses.contains_key(&StatusCode::UNPROCESSABLE_ENTITY)
+				{
 					responses.insert(
-						code,
-						(self.0).openapi_builder.add_schema(E::Output::schema(code))
+						StatusCode::UNPROCESSABLE_ENTITY,
+						OperationResponse::framework(
+							builder.add_schema(ValidationErrors::schema()),
+							vec![APPLICATION_JSON],
+							"The request does not match the schema."
+						)
+					);
+				}
+				self.2.validation =
+					validator.map(|validator| RequestValidation(Arc::new(validator)));
+
+				let has_accepted_types =
+					E::Output::accepted_types().is_some_and(|types| !types.is_empty());
+				if has_accepted_types && !responses.contains_key(&StatusCode::NOT_ACCEPTABLE) {
+					responses.insert(
+						StatusCode::NOT_ACCEPTABLE,
+						OperationResponse::framework(
+							no_content,
+							Vec::new(),
+							"None of the content types of the response are acceptable."
+						)
 					);
 				}
 				let mut path = format!("{}/{}", self.0.scope.unwrap_or_default(), self.1);
-				let mut descr = OperationDescription::new::<E>(responses, &path);
+				let style = builder.operation_id_style;
+				let mut descr = OperationDescription::new::<E>(responses, &path, style);
+				// the tag of the resource is only added to documents that contain one of its operations
+				if let Some(tag) = &self.2.tag {
+					descr.set_default_tag(tag);
+					builder.add_tag(tag, self.2.tag_description.as_deref());
+				}
 				if E::has_placeholders() {
-					descr.set_path_params(E::Placeholders::schema());
+					let mut schema = E::Placeholders::schema();
+					add_fields(&mut schema, E::placeholders_fields());
+					descr.set_path_params(schema);
 				}
 				if E::needs_params() {
-					descr.set_query_params(E::Params::schema());
+					let mut schema = E::Params::schema();
+					add_fields(&mut schema, E::params_fields());
+					descr.set_query_params(schema);
 				}
 				if E::needs_body() {
-					let body_schema = (self.0).openapi_builder.add_schema(E::Body::schema());
+					let mut schema = E::Body::schema();
+					add_fields(&mut schema, E::body_fields());
+					let body_schema = builder.add_schema(schema);
 					descr.set_body::<E::Body>(body_schema);
 				}
 
@@ -125,24 +390,28 @@ macro_rules! implOpenapiRouter {
 					path = format!("{path}/{uri}");
 				}
 
-				let op = descr.into_operation();
-				let mut item = (self.0).openapi_builder.remove_path(&path);
-				match E::http_method() {
-					Method::GET => item.get = Some(op),
-					Method::PUT => item.put = Some(op),
-					Method::POST => item.post = Some(op),
-					Method::DELETE => item.delete = Some(op),
-					Method::OPTIONS => item.options = Some(op),
-					Method::HEAD => item.head = Some(op),
-					Method::PATCH => item.patch = Some(op),
-					Method::TRACE => item.trace = Some(op),
-					method => {
-						warn!("Ignoring unsupported method '{method}' in OpenAPI Specification")
-					}
-				};
-				(self.0).openapi_builder.add_path(path, item);
+				#[cfg(feature = "validate-responses")]
+				{
+					self.2.response_validation = builder.validate_responses.map(|mode| {
+						let endpoint = format!("{} {path}", E::http_method());
+						ResponseValidation(Arc::new(ResponseValidator::new::<E>(endpoint, mode)))
+					});
+				}
+
+				descr.make_operation_id_unique(builder, &path, &E::http_method());
+				builder.add_operation(&path, &E::http_method(), descr.into_operation());
+
+				(&mut *(self.0).router, self.1, &mut *self.2).endpoint::<E>()
+			}
+
+			#[cfg(feature = "cors")]
+			fn cors_config(&mut self, cors: crate::CorsConfig) {
+				(&mut *(self.0).router, self.1, &mut *self.2).cors_config(cors)
+			}
 
-				(&mut *(self.0).router, self.1).endpoint::<E>()
+			fn tag(&mut self, name: &str, description: Option<&str>) {
+				self.2.tag = Some(name.to_owned());
+				self.2.tag_description = description.map(String::from);
 			}
 		}
 	};
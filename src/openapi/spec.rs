@@ -0,0 +1,133 @@
+use super::{
+	builder::{OpenapiBuilder, OpenapiInfo},
+	handler::{prepare_openapi, to_json},
+	router::OpenapiRouter
+};
+use gotham::{
+	router::builder::{build_simple_router, RouterBuilder},
+	state::State
+};
+use openapi_type::openapiv3::OpenAPI;
+
+/// The OpenAPI specification of an API, generated without starting a server. This allows you
+/// to export the specification from a test or a small binary, for example to commit it to your
+/// repository or to generate clients from it.
+///
+/// ```rust
+/// # #[macro_use] extern crate gotham_restful_derive;
+/// # use gotham_restful::*;
+/// # use serde::Serialize;
+/// #[derive(Resource)]
+/// #[resource(read_all)]
+/// struct FooResource;
+///
+/// #[read_all]
+/// fn read_all() -> Success<Vec<String>> {
+/// 	Vec::new().into()
+/// }
+///
+/// let info = OpenapiInfo {
+/// 	title: "My Foo API".to_owned(),
+/// 	version: "0.1.0".to_owned(),
+/// 	urls: vec!["https://example.org/foo/api/v1".to_owned()]
+/// };
+/// let spec = OpenapiSpec::new(info, |mut route| {
+/// 	route.resource::<FooResource>("foo");
+/// });
+/// assert!(spec.openapi().paths.paths.contains_key("/foo"));
+/// let json = spec.to_json().unwrap();
+/// # drop(json);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OpenapiSpec {
+	openapi: OpenAPI,
+	#[cfg(feature = "auth")]
+	auth_source: Option<crate::AuthSource>,
+	#[cfg(feature = "auth")]
+	csrf_config: Option<crate::CsrfConfig>
+}
+
+impl OpenapiSpec {
+	/// Generate the specification by registering all resources inside `block`, just like you
+	/// would inside [with_openapi](crate::WithOpenapi::with_openapi). Handlers registered by the
+	/// router are never called.
+	pub fn new<F>(info: OpenapiInfo, block: F) -> Self
+	where
+		F: FnOnce(OpenapiRouter<'_, RouterBuilder<'_, (), ()>>)
+	{
+		let mut openapi_builder = OpenapiBuilder::new(info);
+		let openapi = openapi_builder.openapi.clone();
+		build_simple_router(|router| {
+			block(OpenapiRouter {
+				router,
+				scope: None,
+				openapi_builder: &mut openapi_builder
+			});
+		});
+
+		let openapi = openapi.read().clone();
+		Self {
+			openapi,
+			#[cfg(feature = "auth")]
+			auth_source: None,
+			#[cfg(feature = "auth")]
+			csrf_config: None
+		}
+	}
+
+	/// Document the authentication that the [AuthMiddleware](crate::AuthMiddleware) of your
+	/// server expects. This is normally taken from the state when serving the specification.
+	#[cfg(feature = "auth")]
+	pub fn with_auth_source(mut self, auth_source: crate::AuthSource) -> Self {
+		self.auth_source = Some(auth_source);
+		self
+	}
+
+	/// Document the header that the [CsrfConfig](crate::CsrfConfig) of your server checks.
+	/// This is normally taken from the state when serving the specification.
+	#[cfg(feature = "auth")]
+	pub fn with_csrf_config(mut self, csrf_config: crate::CsrfConfig) -> Self {
+		self.csrf_config = Some(csrf_config);
+		self
+	}
+
+	/// Return the specification as collected from the resources. This does not include any
+	/// information from [with_auth_source](Self::with_auth_source) or similar methods, and
+	/// always uses the types of OpenAPI 3.0, even if OpenAPI 3.1 was requested using
+	/// [openapi_version](OpenapiRouter::openapi_version). In that case, only its `openapi` field
+	/// is set to `3.1.0`; use [to_json](Self::to_json) to obtain the converted specification.
+	pub fn openapi(&self) -> &OpenAPI {
+		&self.openapi
+	}
+
+	fn prepare(&self) -> OpenAPI {
+		let mut openapi = None;
+		State::with_new(|state| {
+			#[cfg(feature = "auth")]
+			{
+				if let Some(auth_source) = &self.auth_source {
+					state.put(auth_source.clone());
+				}
+				if let Some(csrf_config) = &self.csrf_config {
+					state.put(csrf_config.clone());
+				}
+			}
+			openapi = Some(prepare_openapi(state, &self.openapi));
+		});
+		openapi.unwrap()
+	}
+
+	/// Return the specification as pretty-printed JSON. Apart from the formatting, this is the
+	/// same specification that is served by [openapi_spec](crate::GetOpenapi::openapi_spec),
+	/// including the conversion to OpenAPI 3.1 if requested.
+	pub fn to_json(&self) -> Result<String, serde_json::Error> {
+		to_json(&self.prepare(), true)
+	}
+
+	/// Return the specification as YAML, exactly as it would be served by
+	/// `openapi_spec_yaml`. This method requires the `openapi-yaml` feature.
+	#[cfg(feature = "openapi-yaml")]
+	pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+		super::handler::to_yaml(&self.prepare())
+	}
+}
@@ -0,0 +1,251 @@
+//! Conversion of the OpenAPI 3.0 specification into OpenAPI 3.1. The specification is always
+//! collected using the `openapiv3` types, and converted to 3.1 only when it is being served.
+
+use serde_json::{Map, Value};
+
+pub(crate) const OPENAPI_3_1: &str = "3.1.0";
+
+/// Convert a json-serialized OpenAPI 3.0 specification into OpenAPI 3.1.
+pub(crate) fn convert(openapi: &mut Value) {
+	let Some(openapi) = openapi.as_object_mut() else {
+		return;
+	};
+	openapi.insert("openapi".to_owned(), OPENAPI_3_1.into());
+
+	for (key, value) in openapi.iter_mut() {
+		if key == "components" {
+			convert_components(value);
+		} else {
+			convert_schemas_within(value);
+		}
+	}
+}
+
+fn convert_components(components: &mut Value) {
+	let Some(components) = components.as_object_mut() else {
+		return;
+	};
+	for (key, value) in components.iter_mut() {
+		if key == "schemas" {
+			if let Some(schemas) = value.as_object_mut() {
+				schemas.values_mut().for_each(convert_schema);
+			}
+		} else {
+			convert_schemas_within(value);
+		}
+	}
+}
+
+/// Search for `schema` properties of parameters, headers and media types, and convert them.
+fn convert_schemas_within(value: &mut Value) {
+	match value {
+		Value::Object(obj) => {
+			for (key, value) in obj.iter_mut() {
+				if key == "schema" {
+					convert_schema(value);
+				} else {
+					convert_schemas_within(value);
+				}
+			}
+		},
+		Value::Array(arr) => arr.iter_mut().for_each(convert_schemas_within),
+		_ => {}
+	}
+}
+
+fn convert_schema(schema: &mut Value) {
+	let Some(obj) = schema.as_object_mut() else {
+		return;
+	};
+
+	// convert all subschemas
+	for key in ["items", "not", "additionalProperties"] {
+		if let Some(value) = obj.get_mut(key) {
+			convert_schema(value);
+		}
+	}
+	for key in ["allOf", "anyOf", "oneOf"] {
+		if let Some(Value::Array(arr)) = obj.get_mut(key) {
+			arr.iter_mut().for_each(convert_schema);
+		}
+	}
+	if let Some(Value::Object(props)) = obj.get_mut("properties") {
+		props.values_mut().for_each(convert_schema);
+	}
+
+	// exclusiveMinimum and exclusiveMaximum are numbers instead of booleans
+	convert_exclusive(obj, "minimum", "exclusiveMinimum");
+	convert_exclusive(obj, "maximum", "exclusiveMaximum");
+
+	// example is deprecated in favour of examples
+	if let Some(example) = obj.remove("example") {
+		obj.insert("examples".to_owned(), Value::Array(vec![example]));
+	}
+
+	// nullable has been replaced by the null type
+	if let Some(nullable) = obj.remove("nullable") {
+		if nullable == Value::Bool(true) {
+			make_nullable(schema);
+		}
+	}
+}
+
+fn convert_exclusive(obj: &mut Map<String, Value>, inclusive: &str, exclusive: &str) {
+	match obj.get(exclusive) {
+		Some(Value::Bool(true)) => match obj.remove(inclusive) {
+			Some(bound) => {
+				obj.insert(exclusive.to_owned(), bound);
+			},
+			None => {
+				obj.remove(exclusive);
+			}
+		},
+		Some(Value::Bool(false)) => {
+			obj.remove(exclusive);
+		},
+		_ => {}
+	}
+}
+
+fn make_nullable(schema: &mut Value) {
+	let Value::Object(obj) = schema else {
+		return;
+	};
+
+	if let Some(ty) = obj.get_mut("type") {
+		if let Value::String(name) = ty {
+			*ty = Value::Array(vec![Value::String(name.clone()), "null".into()]);
+		}
+		if let Some(Value::Array(values)) = obj.get_mut("enum") {
+			if !values.contains(&Value::Null) {
+				values.push(Value::Null);
+			}
+		}
+		return;
+	}
+
+	// schemas without a type (like references) need to be wrapped
+	let inner = match obj.remove("allOf") {
+		Some(Value::Array(mut all_of)) if obj.is_empty() && all_of.len() == 1 => all_of.remove(0),
+		Some(all_of) => {
+			obj.insert("allOf".to_owned(), all_of);
+			Value::Object(std::mem::take(obj))
+		},
+		None => Value::Object(std::mem::take(obj))
+	};
+	let mut null = Map::new();
+	null.insert("type".to_owned(), "null".into());
+	obj.insert(
+		"anyOf".to_owned(),
+		Value::Array(vec![inner, Value::Object(null)])
+	);
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn convert_document() {
+		let mut openapi = json!({
+			"openapi": "3.0.2",
+			"paths": {
+				"/foo": {
+					"get": {
+						"parameters": [{
+							"name": "limit",
+							"in": "query",
+							"schema": {
+								"type": "integer",
+								"minimum": 0,
+								"exclusiveMinimum": true,
+								"maximum": 100,
+								"exclusiveMaximum": false
+							}
+						}]
+					}
+				}
+			},
+			"components": {
+				"schemas": {
+					"Foo": {
+						"type": "object",
+						"properties": {
+							"nullable": {
+								"type": "string",
+								"nullable": true,
+								"example": "foo"
+							},
+							"choice": {
+								"type": "string",
+								"enum": ["a", "b"],
+								"nullable": true
+							},
+							"reference": {
+								"$ref": "#/components/schemas/Bar",
+								"nullable": true
+							},
+							"all_of": {
+								"allOf": [{ "$ref": "#/components/schemas/Bar" }],
+								"nullable": true
+							}
+						}
+					}
+				}
+			}
+		});
+		convert(&mut openapi);
+
+		assert_eq!(
+			openapi,
+			json!({
+				"openapi": "3.1.0",
+				"paths": {
+					"/foo": {
+						"get": {
+							"parameters": [{
+								"name": "limit",
+								"in": "query",
+								"schema": {
+									"type": "integer",
+									"exclusiveMinimum": 0,
+									"maximum": 100
+								}
+							}]
+						}
+					}
+				},
+				"components": {
+					"schemas": {
+						"Foo": {
+							"type": "object",
+							"properties": {
+								"nullable": {
+									"type": ["string", "null"],
+									"examples": ["foo"]
+								},
+								"choice": {
+									"type": ["string", "null"],
+									"enum": ["a", "b", null]
+								},
+								"reference": {
+									"anyOf": [
+										{ "$ref": "#/components/schemas/Bar" },
+										{ "type": "null" }
+									]
+								},
+								"all_of": {
+									"anyOf": [
+										{ "$ref": "#/components/schemas/Bar" },
+										{ "type": "null" }
+									]
+								}
+							}
+						}
+					}
+				}
+			})
+		);
+	}
+}
@@ -0,0 +1,775 @@
+use crate::{EndpointWithSchema, RequestBody, Response};
+use gotham::{
+	hyper::{StatusCode, Uri},
+	mime::{Mime, JSON},
+	prelude::StateData,
+	state::{FromState, State}
+};
+use lazy_regex::Regex;
+use openapi_type::{OpenapiSchema, OpenapiType};
+use percent_encoding::percent_decode_str;
+use serde_json::{Map, Value};
+use std::{collections::HashMap, sync::Arc};
+
+/// The json returned with a _422 Unprocessable Entity_ status when a request does not match the
+/// schema of the endpoint.
+#[derive(Debug, OpenapiType, Serialize)]
+pub(crate) struct ValidationErrors {
+	/// This is always `true` and can be used to detect an error response without looking at the
+	/// HTTP status code.
+	error: bool,
+	/// The error message.
+	message: String,
+	/// All values of the request that violate the schema.
+	errors: Vec<ValidationError>
+}
+
+/// A single value of the request that violates the schema.
+#[derive(Debug, OpenapiType, Serialize)]
+pub(crate) struct ValidationError {
+	/// The part of the request that contains the value: `path`, `query` or `body`.
+	pub(crate) location: &'static str,
+	/// A JSON pointer to the value within that part of the request.
+	pub(crate) pointer: String,
+	/// A description of the violation.
+	pub(crate) message: String
+}
+
+/// The placeholders of the path of an endpoint, and their schema.
+#[derive(Debug)]
+struct PathParams {
+	/// The segments of the path template the endpoint was registered with, including the
+	/// scope, with the name of the placeholder for placeholder segments.
+	segments: Vec<Option<String>>,
+	schema: Value
+}
+
+/// The named schemas referenced by the schemas of an endpoint, and the compiled patterns of
+/// all of these schemas.
+#[derive(Debug, Default)]
+pub(crate) struct Schemas {
+	schemas: HashMap<String, Value>,
+	patterns: HashMap<String, Regex>
+}
+
+/// Validates requests against the schemas of an endpoint.
+#[derive(Debug)]
+pub(crate) struct RequestValidator {
+	schemas: Schemas,
+	path: Option<PathParams>,
+	query: Option<Value>,
+	body: Option<Value>
+}
+
+/// The [RequestValidator] of the current endpoint, if request validation is enabled.
+#[derive(Clone, Debug, StateData)]
+pub(crate) struct RequestValidation(pub(crate) Arc<RequestValidator>);
+
+fn escape_pointer(key: &str) -> String {
+	key.replace('~', "~0").replace('/', "~1")
+}
+
+fn percent_decode(value: &str) -> String {
+	percent_decode_str(&value.replace('+', " "))
+		.decode_utf8_lossy()
+		.into_owned()
+}
+
+/// Whether `mime` is `application/json` or another json type like `application/problem+json`.
+pub(crate) fn is_json(mime: &Mime) -> bool {
+	mime.subtype() == JSON || mime.suffix() == Some(JSON)
+}
+
+fn collect_patterns(value: &Value, patterns: &mut HashMap<String, Regex>) {
+	match value {
+		Value::Object(obj) => {
+			if let Some(Value::String(pattern)) = obj.get("pattern") {
+				if !patterns.contains_key(pattern) {
+					match Regex::new(pattern) {
+						Ok(regex) => {
+							patterns.insert(pattern.clone(), regex);
+						},
+						Err(err) => warn!("Ignoring invalid pattern {pattern:?}: {err}")
+					}
+				}
+			}
+			obj.values()
+				.for_each(|value| collect_patterns(value, patterns));
+		},
+		Value::Array(arr) => arr
+			.iter()
+			.for_each(|value| collect_patterns(value, patterns)),
+		_ => {}
+	}
+}
+
+impl Schemas {
+	/// Convert `schema` to json and remember all of its dependencies.
+	pub(crate) fn add_schema(&mut self, mut schema: OpenapiSchema) -> Value {
+		let mut dependencies = std::mem::take(&mut schema.dependencies);
+		while let Some((name, mut dependency)) = dependencies.pop() {
+			dependencies.extend(std::mem::take(&mut dependency.dependencies));
+			if let Ok(value) = serde_json::to_value(dependency.schema) {
+				self.schemas.insert(name, value);
+			}
+		}
+		serde_json::to_value(schema.schema).unwrap_or_default()
+	}
+
+	/// Compile the patterns of the named schemas and of all `schemas`.
+	pub(crate) fn compile_patterns<'a>(&mut self, schemas: impl IntoIterator<Item = &'a Value>) {
+		let mut patterns = HashMap::new();
+		for schema in schemas {
+			collect_patterns(schema, &mut patterns);
+		}
+		for schema in self.schemas.values() {
+			collect_patterns(schema, &mut patterns);
+		}
+		self.patterns = patterns;
+	}
+
+	fn resolve<'a>(&'a self, mut schema: &'a Value) -> &'a Value {
+		// limit the number of references to follow in case they are cyclic
+		for _ in 0..32 {
+			let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+				return schema;
+			};
+			match reference
+				.strip_prefix("#/components/schemas/")
+				.and_then(|name| self.schemas.get(name))
+			{
+				Some(referenced) => schema = referenced,
+				None => return &Value::Null
+			}
+		}
+		&Value::Null
+	}
+
+	/// Validate `value` against `schema`. Returns all violations of the schema.
+	pub(crate) fn validate(
+		&self,
+		location: &'static str,
+		schema: &Value,
+		value: &Value
+	) -> Vec<ValidationError> {
+		let mut errors = Vec::new();
+		Validation::new(self, location, &mut errors).validate(schema, value, "");
+		errors
+	}
+}
+
+impl RequestValidator {
+	/// Create a validator for the endpoint `E` registered at `uri`, which is the full path
+	/// template including the scope. Returns [None] if the endpoint has nothing that could be
+	/// validated.
+	pub(crate) fn new<E: EndpointWithSchema>(uri: &str) -> Option<Self> {
+		let mut validator = Self {
+			schemas: Schemas::default(),
+			path: None,
+			query: None,
+			body: None
+		};
+
+		// globs cannot be matched to the segments of the request path
+		if E::has_placeholders() && !uri.contains('*') {
+			let segments = uri
+				.split('/')
+				.filter(|segment| !segment.is_empty())
+				.map(|segment| {
+					let name = segment.strip_prefix(':')?;
+					Some(name.split(':').next().unwrap_or(name).to_owned())
+				})
+				.collect();
+			let schema = validator.schemas.add_schema(E::Placeholders::schema());
+			validator.path = Some(PathParams { segments, schema });
+		}
+		if E::needs_params() {
+			validator.query = Some(validator.schemas.add_schema(E::Params::schema()));
+		}
+		if E::needs_body() {
+			let accepts_json = E::Body::supported_types()
+				.map(|types| types.iter().any(is_json))
+				.unwrap_or(true);
+			if accepts_json {
+				validator.body = Some(validator.schemas.add_schema(E::Body::schema()));
+			}
+		}
+
+		if validator.path.is_none() && validator.query.is_none() && validator.body.is_none() {
+			return None;
+		}
+
+		let roots = [
+			validator.path.as_ref().map(|path| &path.schema),
+			validator.query.as_ref(),
+			validator.body.as_ref()
+		];
+		validator
+			.schemas
+			.compile_patterns(roots.into_iter().flatten());
+		Some(validator)
+	}
+
+	fn resolve<'a>(&'a self, schema: &'a Value) -> &'a Value {
+		self.schemas.resolve(schema)
+	}
+
+	/// Convert a string value from the path or query string to the type of the schema.
+	fn coerce(&self, schema: &Value, value: String) -> Value {
+		let schema = self.resolve(schema);
+		match schema.get("type").and_then(Value::as_str) {
+			Some("integer") => value
+				.parse::<i64>()
+				.map(Value::from)
+				.or_else(|_| value.parse::<u64>().map(Value::from))
+				.unwrap_or(Value::String(value)),
+			Some("number") => value
+				.parse::<f64>()
+				.map(Value::from)
+				.unwrap_or(Value::String(value)),
+			Some("boolean") => match value.as_str() {
+				"true" => Value::Bool(true),
+				"false" => Value::Bool(false),
+				_ => Value::String(value)
+			},
+			_ => Value::String(value)
+		}
+	}
+
+	fn property_schema<'a>(&'a self, schema: &'a Value, name: &str) -> &'a Value {
+		self.resolve(schema)
+			.get("properties")
+			.and_then(|properties| properties.get(name))
+			.unwrap_or(&Value::Null)
+	}
+
+	fn path_params(&self, path: &PathParams, request_path: &str) -> Value {
+		let segments = request_path
+			.split('/')
+			.filter(|segment| !segment.is_empty());
+		let mut params = Map::new();
+		for (name, value) in path.segments.iter().zip(segments) {
+			if let Some(name) = name {
+				// placeholders of the scope are not part of the endpoint's placeholders
+				let schema = self.property_schema(&path.schema, name);
+				if schema.is_null() {
+					continue;
+				}
+				let value = self.coerce(schema, percent_decode(value));
+				params.insert(name.clone(), value);
+			}
+		}
+		Value::Object(params)
+	}
+
+	fn query_params(&self, schema: &Value, query: &str) -> Value {
+		let mut values: Vec<(String, Vec<String>)> = Vec::new();
+		for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+			let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+			let (name, value) = (percent_decode(name), percent_decode(value));
+			match values.iter_mut().find(|(n, _)| *n == name) {
+				Some((_, vals)) => vals.push(value),
+				None => values.push((name, vec![value]))
+			}
+		}
+
+		let mut params = Map::new();
+		for (name, vals) in values {
+			let schema = self.resolve(self.property_schema(schema, &name));
+			let value = match schema.get("type").and_then(Value::as_str) {
+				Some("array") => {
+					let items = schema.get("items").unwrap_or(&Value::Null);
+					Value::Array(vals.into_iter().map(|v| self.coerce(items, v)).collect())
+				},
+				_ => self.coerce(schema, vals.into_iter().next().unwrap_or_default())
+			};
+			params.insert(name, value);
+		}
+		Value::Object(params)
+	}
+
+	/// Validate the request in `state` and its `body`. Returns all violations of the schemas.
+	fn validate(&self, state: &State, body: Option<(&[u8], &Mime)>) -> Vec<ValidationError> {
+		let uri = Uri::borrow_from(state);
+		let mut errors = Vec::new();
+
+		if let Some(path) = &self.path {
+			let params = self.path_params(path, uri.path());
+			errors.extend(self.schemas.validate("path", &path.schema, &params));
+		}
+		if let Some(schema) = &self.query {
+			let params = self.query_params(schema, uri.query().unwrap_or_default());
+			errors.extend(self.schemas.validate("query", schema, &params));
+		}
+		if let (Some(schema), Some((body, mime))) = (&self.body, body) {
+			// invalid json is rejected when parsing the body
+			if is_json(mime) {
+				if let Ok(body) = serde_json::from_slice::<Value>(body) {
+					errors.extend(self.schemas.validate("body", schema, &body));
+				}
+			}
+		}
+
+		errors
+	}
+}
+
+struct Validation<'a> {
+	schemas: &'a Schemas,
+	location: &'static str,
+	errors: &'a mut Vec<ValidationError>
+}
+
+impl<'a> Validation<'a> {
+	fn new(
+		schemas: &'a Schemas,
+		location: &'static str,
+		errors: &'a mut Vec<ValidationError>
+	) -> Self {
+		Self {
+			schemas,
+			location,
+			errors
+		}
+	}
+
+	fn error(&mut self, pointer: &str, message: String) {
+		self.errors.push(ValidationError {
+			location: self.location,
+			pointer: pointer.to_owned(),
+			message
+		});
+	}
+
+	fn matches(&self, schema: &Value, value: &Value) -> bool {
+		let mut errors = Vec::new();
+		Validation::new(self.schemas, self.location, &mut errors).validate(schema, value, "");
+		errors.is_empty()
+	}
+
+	fn validate(&mut self, schema: &Value, value: &Value, pointer: &str) {
+		let Some(schema) = self.schemas.resolve(schema).as_object() else {
+			return;
+		};
+		if value.is_null() && schema.get("nullable") == Some(&Value::Bool(true)) {
+			return;
+		}
+
+		if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+			if !values.contains(value) {
+				let values = values.iter().map(Value::to_string).collect::<Vec<_>>();
+				self.error(pointer, format!("must be one of {}", values.join(", ")));
+				return;
+			}
+		}
+
+		for schema in schema
+			.get("allOf")
+			.and_then(Value::as_array)
+			.into_iter()
+			.flatten()
+		{
+			self.validate(schema, value, pointer);
+		}
+		if let Some(schemas) = schema.get("anyOf").and_then(Value::as_array) {
+			if !schemas.iter().any(|schema| self.matches(schema, value)) {
+				self.error(pointer, "must match at least one of the schemas".to_owned());
+			}
+		}
+		if let Some(schemas) = schema.get("oneOf").and_then(Value::as_array) {
+			let count = schemas
+				.iter()
+				.filter(|schema| self.matches(schema, value))
+				.count();
+			if count != 1 {
+				self.error(pointer, "must match exactly one of the schemas".to_owned());
+			}
+		}
+		if let Some(not) = schema.get("not") {
+			if self.matches(not, value) {
+				self.error(pointer, "must not match the schema".to_owned());
+			}
+		}
+
+		let is_type = match schema.get("type").and_then(Value::as_str) {
+			Some("string") => value.is_string(),
+			Some("integer") => {
+				value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+			},
+			Some("number") => value.is_number(),
+			Some("boolean") => value.is_boolean(),
+			Some("array") => value.is_array(),
+			Some("object") => value.is_object(),
+			_ => true
+		};
+		if !is_type {
+			let ty = schema["type"].as_str().unwrap_or_default();
+			let article = if ty.starts_with(['a', 'i', 'o']) {
+				"an"
+			} else {
+				"a"
+			};
+			self.error(pointer, format!("must be {article} {ty}"));
+			return;
+		}
+
+		match value {
+			Value::String(value) => self.validate_string(schema, value, pointer),
+			Value::Number(value) => self.validate_number(schema, value.as_f64(), pointer),
+			Value::Array(value) => self.validate_array(schema, value, pointer),
+			Value::Object(value) => self.validate_object(schema, value, pointer),
+			_ => {}
+		}
+	}
+
+	fn validate_string(&mut self, schema: &Map<String, Value>, value: &str, pointer: &str) {
+		let len = value.chars().count() as u64;
+		if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+			if len < min {
+				self.error(pointer, format!("must be at least {min} characters long"));
+			}
+		}
+		if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+			if len > max {
+				self.error(pointer, format!("must be at most {max} characters long"));
+			}
+		}
+		if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+			let regex = self.schemas.patterns.get(pattern);
+			if regex.is_some_and(|regex| !regex.is_match(value)) {
+				self.error(pointer, format!("must match the pattern {pattern:?}"));
+			}
+		}
+	}
+
+	fn validate_number(&mut self, schema: &Map<String, Value>, value: Option<f64>, pointer: &str) {
+		let Some(value) = value else {
+			return;
+		};
+		if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+			match schema.get("exclusiveMinimum") {
+				Some(Value::Bool(true)) if value <= min => self.error(
+					pointer,
+					format!("must be greater than {}", schema["minimum"])
+				),
+				Some(Value::Bool(true)) => {},
+				_ if value < min => {
+					self.error(pointer, format!("must be at least {}", schema["minimum"]))
+				},
+				_ => {}
+			}
+		}
+		if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+			match schema.get("exclusiveMaximum") {
+				Some(Value::Bool(true)) if value >= max => {
+					self.error(pointer, format!("must be less than {}", schema["maximum"]))
+				},
+				Some(Value::Bool(true)) => {},
+				_ if value > max => {
+					self.error(pointer, format!("must be at most {}", schema["maximum"]))
+				},
+				_ => {}
+			}
+		}
+		if let Some(multiple_of) = schema.get("multipleOf").and_then(Value::as_f64) {
+			if multiple_of > 0.0 && (value / multiple_of).fract() != 0.0 {
+				self.error(
+					pointer,
+					format!("must be a multiple of {}", schema["multipleOf"])
+				);
+			}
+		}
+	}
+
+	fn validate_array(&mut self, schema: &Map<String, Value>, value: &[Value], pointer: &str) {
+		let len = value.len() as u64;
+		if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+			if len < min {
+				self.error(pointer, format!("must contain at least {min} items"));
+			}
+		}
+		if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+			if len > max {
+				self.error(pointer, format!("must contain at most {max} items"));
+			}
+		}
+		if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+			let has_duplicates = value
+				.iter()
+				.enumerate()
+				.any(|(i, item)| value[..i].contains(item));
+			if has_duplicates {
+				self.error(pointer, "must not contain duplicate items".to_owned());
+			}
+		}
+		if let Some(items) = schema.get("items") {
+			for (i, item) in value.iter().enumerate() {
+				self.validate(items, item, &format!("{pointer}/{i}"));
+			}
+		}
+	}
+
+	fn validate_object(
+		&mut self,
+		schema: &Map<String, Value>,
+		value: &Map<String, Value>,
+		pointer: &str
+	) {
+		let len = value.len() as u64;
+		if let Some(min) = schema.get("minProperties").and_then(Value::as_u64) {
+			if len < min {
+				self.error(pointer, format!("must contain at least {min} properties"));
+			}
+		}
+		if let Some(max) = schema.get("maxProperties").and_then(Value::as_u64) {
+			if len > max {
+				self.error(pointer, format!("must contain at most {max} properties"));
+			}
+		}
+		for name in schema
+			.get("required")
+			.and_then(Value::as_array)
+			.into_iter()
+			.flatten()
+		{
+			if let Some(name) = name.as_str() {
+				if !value.contains_key(name) {
+					let pointer = format!("{pointer}/{}", escape_pointer(name));
+					self.error(&pointer, "is required".to_owned());
+				}
+			}
+		}
+
+		let properties = schema.get("properties").and_then(Value::as_object);
+		let additional = schema.get("additionalProperties");
+		for (name, value) in value {
+			let pointer = format!("{pointer}/{}", escape_pointer(name));
+			match properties.and_then(|properties| properties.get(name)) {
+				Some(property) => self.validate(property, value, &pointer),
+				None => match additional {
+					Some(Value::Bool(false)) => self.error(&pointer, "is not allowed".to_owned()),
+					Some(additional @ Value::Object(_)) => {
+						self.validate(additional, value, &pointer)
+					},
+					_ => {}
+				}
+			}
+		}
+	}
+}
+
+/// Validate the request in `state` and its `body` if request validation is enabled for the
+/// endpoint. Returns a _422 Unprocessable Entity_ response if the request is invalid.
+pub(crate) fn validate_request(
+	state: &State,
+	body: Option<(&[u8], &Mime)>
+) -> Result<Option<Response>, serde_json::Error> {
+	let Some(RequestValidation(validator)) = RequestValidation::try_borrow_from(state) else {
+		return Ok(None);
+	};
+	let errors = validator.validate(state, body);
+	if errors.is_empty() {
+		return Ok(None);
+	}
+
+	debug!("Invalid Request: Returning 422 Response");
+	let errors = ValidationErrors {
+		error: true,
+		message: "The request does not match the schema".to_owned(),
+		errors
+	};
+	Ok(Some(Response::json(
+		StatusCode::UNPROCESSABLE_ENTITY,
+		serde_json::to_string(&errors)?
+	)))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use openapi_type::openapiv3::{
+		ObjectType, ReferenceOr::Item, Schema, SchemaData, SchemaKind, StringType, Type
+	};
+	use serde_json::json;
+
+	fn validator(schema: Value, patterns: &[&str]) -> (RequestValidator, Value) {
+		let mut validator = RequestValidator {
+			schemas: Schemas::default(),
+			path: None,
+			query: None,
+			body: None
+		};
+		validator.schemas.patterns = patterns
+			.iter()
+			.map(|pattern| (pattern.to_string(), Regex::new(pattern).unwrap()))
+			.collect();
+		(validator, schema)
+	}
+
+	fn errors(validator: &RequestValidator, schema: &Value, value: Value) -> Vec<(String, String)> {
+		validator
+			.schemas
+			.validate("body", schema, &value)
+			.into_iter()
+			.map(|err| (err.pointer, err.message))
+			.collect()
+	}
+
+	#[test]
+	fn validate_object() {
+		let (validator, schema) = validator(
+			json!({
+				"type": "object",
+				"properties": {
+					"name": { "type": "string", "minLength": 3, "pattern": "^[a-z]+$" },
+					"age": { "type": "integer", "minimum": 0, "maximum": 150 },
+					"role": { "type": "string", "enum": ["admin", "user"] },
+					"tags": { "type": "array", "items": { "type": "string", "maxLength": 2 } }
+				},
+				"required": ["name", "role"],
+				"additionalProperties": false
+			}),
+			&["^[a-z]+$"]
+		);
+
+		assert_eq!(
+			errors(
+				&validator,
+				&schema,
+				json!({ "name": "bob", "role": "user" })
+			),
+			Vec::<(String, String)>::new()
+		);
+		assert_eq!(
+			errors(
+				&validator,
+				&schema,
+				json!({ "name": "Al", "age": 200, "tags": ["ab", "abc"], "a/b": 1 })
+			),
+			vec![
+				("/role".to_owned(), "is required".to_owned()),
+				("/a~1b".to_owned(), "is not allowed".to_owned()),
+				("/age".to_owned(), "must be at most 150".to_owned()),
+				(
+					"/name".to_owned(),
+					"must be at least 3 characters long".to_owned()
+				),
+				(
+					"/name".to_owned(),
+					r#"must match the pattern "^[a-z]+$""#.to_owned()
+				),
+				(
+					"/tags/1".to_owned(),
+					"must be at most 2 characters long".to_owned()
+				)
+			]
+		);
+		assert_eq!(
+			errors(
+				&validator,
+				&schema,
+				json!({ "name": "bob", "role": "guest" })
+			),
+			vec![(
+				"/role".to_owned(),
+				r#"must be one of "admin", "user""#.to_owned()
+			)]
+		);
+		assert_eq!(errors(&validator, &schema, json!([])), vec![(
+			"".to_owned(),
+			"must be an object".to_owned()
+		)]);
+	}
+
+	#[test]
+	fn validate_nullable_reference() {
+		let mut validator = validator(Value::Null, &[]).0;
+		validator.schemas.schemas.insert(
+			"Name".to_owned(),
+			json!({ "type": "string", "maxLength": 4 })
+		);
+		let schema = json!({
+			"type": "object",
+			"properties": {
+				"name": { "nullable": true, "allOf": [{ "$ref": "#/components/schemas/Name" }] }
+			}
+		});
+
+		assert!(errors(&validator, &schema, json!({ "name": null })).is_empty());
+		assert_eq!(
+			errors(&validator, &schema, json!({ "name": "Alice" })),
+			vec![(
+				"/name".to_owned(),
+				"must be at most 4 characters long".to_owned()
+			)]
+		);
+	}
+
+	#[test]
+	fn coerce_query_params() {
+		let schema = Schema {
+			schema_data: SchemaData::default(),
+			schema_kind: SchemaKind::Type(Type::Object(ObjectType {
+				properties: [
+					(
+						"name".to_owned(),
+						Item(Box::new(Schema {
+							schema_data: SchemaData::default(),
+							schema_kind: SchemaKind::Type(Type::String(StringType::default()))
+						}))
+					),
+					(
+						"id".to_owned(),
+						Item(Box::new(<u64 as OpenapiType>::schema().schema))
+					),
+					(
+						"flags".to_owned(),
+						Item(Box::new(<Vec<bool> as OpenapiType>::schema().schema))
+					)
+				]
+				.into_iter()
+				.collect(),
+				..Default::default()
+			}))
+		};
+		let (validator, schema) = validator(serde_json::to_value(schema).unwrap(), &[]);
+
+		assert_eq!(
+			validator.query_params(&schema, "name=a+b%21&id=42&flags=true&flags=false&x="),
+			json!({
+				"name": "a b!",
+				"id": 42,
+				"flags": [true, false],
+				"x": ""
+			})
+		);
+	}
+
+	#[test]
+	fn path_params_in_scope() {
+		let (validator, schema) = validator(
+			json!({
+				"type": "object",
+				"properties": {
+					"id": { "type": "integer" }
+				}
+			}),
+			&[]
+		);
+		let path = PathParams {
+			segments: vec![
+				None,
+				Some("tenant".to_owned()),
+				None,
+				Some("id".to_owned()),
+				None,
+			],
+			schema
+		};
+
+		assert_eq!(
+			validator.path_params(&path, "/api/acme/foo/42/details"),
+			json!({ "id": 42 })
+		);
+	}
+}
@@ -1,29 +1,138 @@
+use super::ResourceError;
 use crate::{IntoResponseError, Response};
-use gotham::{hyper::StatusCode, mime::TEXT_PLAIN_UTF_8};
+use gotham::{hyper::StatusCode, prelude::*};
 use gotham_restful_derive::ResourceError;
 #[cfg(feature = "openapi")]
 use openapi_type::{OpenapiSchema, OpenapiType};
+use std::fmt::{self, Display};
+
+/// The reason why a request could not be authenticated. When the auth middleware fails to
+/// authenticate a request, it puts this type into gotham's [State](gotham::state::State) in
+/// addition to the [AuthStatus](crate::AuthStatus), so that other middlewares (e.g. for logging)
+/// can access it without knowing the type of the claims.
+///
+/// Every failure has a stable, machine-readable [code](Self::code) that is included in the body
+/// of the _403 Forbidden_ response produced by [AuthError].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, StateData)]
+#[non_exhaustive]
+pub enum AuthFailure {
+	/// The request did not contain a token.
+	MissingToken,
+	/// The token could not be decoded.
+	Malformed,
+	/// The signature of the token could not be verified.
+	BadSignature,
+	/// The token has expired.
+	Expired,
+	/// The token is not valid yet.
+	NotYetValid,
+	/// The token was issued for a different audience.
+	WrongAudience,
+	/// The token was issued by an untrusted issuer.
+	WrongIssuer,
+	/// The [AuthHandler](crate::AuthHandler) did not provide a key to verify the token after
+	/// looking at its claims.
+	UnknownKey,
+	/// No secret was available to verify the token, e.g. because looking it up failed.
+	NoSecret,
+	/// The token has been revoked.
+	Revoked,
+	/// The token was rejected for any other reason.
+	Invalid
+}
+
+impl AuthFailure {
+	/// Return the machine-readable code of this failure. These codes are guaranteed to never
+	/// change.
+	pub fn code(self) -> &'static str {
+		match self {
+			Self::MissingToken => "missing_token",
+			Self::Malformed => "malformed_token",
+			Self::BadSignature => "bad_signature",
+			Self::Expired => "token_expired",
+			Self::NotYetValid => "token_not_yet_valid",
+			Self::WrongAudience => "wrong_audience",
+			Self::WrongIssuer => "wrong_issuer",
+			Self::UnknownKey => "unknown_key",
+			Self::NoSecret => "no_secret",
+			Self::Revoked => "token_revoked",
+			Self::Invalid => "invalid_token"
+		}
+	}
+}
+
+impl Display for AuthFailure {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::MissingToken => "Missing token",
+			Self::Malformed => "Malformed token",
+			Self::BadSignature => "Invalid token signature",
+			Self::Expired => "Expired token",
+			Self::NotYetValid => "Token not valid yet",
+			Self::WrongAudience => "Invalid token audience",
+			Self::WrongIssuer => "Invalid token issuer",
+			Self::UnknownKey => "Unknown token key",
+			Self::NoSecret => "The authentication could not be determined",
+			Self::Revoked => "Revoked token",
+			Self::Invalid => "Invalid token"
+		})
+	}
+}
 
 /// This is an error type that always yields a _403 Forbidden_ response. This type
 /// is best used in combination with [`AuthSuccess`] or [`AuthResult`].
+///
+/// The response body is a json object containing the error message and, if the error was
+/// created from an [AuthFailure], its [code](AuthFailure::code):
+///
+/// ```json
+/// {"error":true,"message":"Expired token","code":"token_expired"}
+/// ```
 #[derive(Clone, Debug)]
-pub struct AuthError(String);
+pub struct AuthError {
+	msg: String,
+	failure: Option<AuthFailure>
+}
 
 impl AuthError {
 	pub fn new<T: Into<String>>(msg: T) -> Self {
-		Self(msg.into())
+		Self {
+			msg: msg.into(),
+			failure: None
+		}
+	}
+
+	/// Create a new [AuthError] with a custom message for the given failure.
+	pub fn with_failure<T: Into<String>>(failure: AuthFailure, msg: T) -> Self {
+		Self {
+			msg: msg.into(),
+			failure: Some(failure)
+		}
+	}
+
+	/// Return the reason of this error, if known.
+	pub fn failure(&self) -> Option<AuthFailure> {
+		self.failure
+	}
+}
+
+impl From<AuthFailure> for AuthError {
+	fn from(failure: AuthFailure) -> Self {
+		Self::with_failure(failure, failure.to_string())
 	}
 }
 
 impl IntoResponseError for AuthError {
-	// TODO why does this need to be serde_json::Error ?!?
 	type Err = serde_json::Error;
 
 	fn into_response_error(self) -> Result<Response, Self::Err> {
-		Ok(Response::new(
+		let mut err: ResourceError = self.msg.into();
+		if let Some(failure) = self.failure {
+			err = err.with_code(failure.code());
+		}
+		Ok(Response::json(
 			StatusCode::FORBIDDEN,
-			self.0,
-			Some(TEXT_PLAIN_UTF_8)
+			serde_json::to_string(&err)?
 		))
 	}
 
@@ -35,7 +144,7 @@ impl IntoResponseError for AuthError {
 	#[cfg(feature = "openapi")]
 	fn schema(code: StatusCode) -> OpenapiSchema {
 		assert_eq!(code, StatusCode::FORBIDDEN);
-		<super::Raw<String> as OpenapiType>::schema()
+		ResourceError::schema()
 	}
 }
 
@@ -1,11 +1,13 @@
 use futures_util::future::{self, BoxFuture, FutureExt};
+#[cfg(any(feature = "openapi", test))]
+use gotham::mime::STAR_STAR;
 use gotham::{
 	handler::HandlerError,
 	hyper::{
 		header::{HeaderMap, HeaderName, HeaderValue},
 		Body, StatusCode
 	},
-	mime::{Mime, APPLICATION_JSON, STAR_STAR}
+	mime::{Mime, APPLICATION_JSON}
 };
 #[cfg(feature = "openapi")]
 use openapi_type::{OpenapiSchema, OpenapiType};
@@ -16,7 +18,7 @@ use std::{convert::Infallible, fmt::Debug, future::Future, pin::Pin};
 
 mod auth_result;
 #[allow(unreachable_pub)]
-pub use auth_result::{AuthError, AuthErrorOrOther, AuthResult, AuthSuccess};
+pub use auth_result::{AuthError, AuthErrorOrOther, AuthFailure, AuthResult, AuthSuccess};
 
 mod no_content;
 #[allow(unreachable_pub)]
@@ -38,10 +40,12 @@ mod success;
 #[allow(unreachable_pub)]
 pub use success::Success;
 
+#[cfg(any(feature = "openapi", test))]
 pub(crate) trait OrAllTypes {
 	fn or_all_types(self) -> Vec<Mime>;
 }
 
+#[cfg(any(feature = "openapi", test))]
 impl OrAllTypes for Option<Vec<Mime>> {
 	fn or_all_types(self) -> Vec<Mime> {
 		self.unwrap_or_else(|| vec![STAR_STAR])
@@ -193,6 +197,64 @@ pub trait ResponseSchema {
 	/// only be one that was previously returned by [Self::status_codes]. The
 	/// implementation should panic if that is not the case.
 	fn schema(code: StatusCode) -> OpenapiSchema;
+
+	/// Return the headers of the response for the given status code. The code may
+	/// only be one that was previously returned by [Self::status_codes]. Returns no
+	/// headers by default.
+	fn headers(_code: StatusCode) -> Vec<ResponseHeader> {
+		Vec::new()
+	}
+
+	/// Return the description of the response for the given status code. The code may
+	/// only be one that was previously returned by [Self::status_codes]. Returns [None]
+	/// by default, in which case the canonical reason of the status code is used.
+	fn description(_code: StatusCode) -> Option<String> {
+		None
+	}
+}
+
+/// A header of a response that is documented in the OpenAPI specification. See
+/// [ResponseSchema::headers].
+#[cfg(feature = "openapi")]
+#[derive(Debug)]
+pub struct ResponseHeader {
+	pub name: HeaderName,
+	pub description: Option<String>,
+	/// The schema of the header value. This is a string by default.
+	pub schema: OpenapiSchema,
+	/// Whether the header is always present in the response.
+	pub required: bool
+}
+
+#[cfg(feature = "openapi")]
+impl ResponseHeader {
+	/// Create a new, optional header with a string value.
+	pub fn new(name: HeaderName) -> Self {
+		Self {
+			name,
+			description: None,
+			schema: String::schema(),
+			required: false
+		}
+	}
+
+	/// Add a description to this header.
+	pub fn with_description<D: Into<String>>(mut self, description: D) -> Self {
+		self.description = Some(description.into());
+		self
+	}
+
+	/// Use a different schema for the value of this header.
+	pub fn with_schema(mut self, schema: OpenapiSchema) -> Self {
+		self.schema = schema;
+		self
+	}
+
+	/// Mark this header as always present in the response.
+	pub fn required(mut self) -> Self {
+		self.required = true;
+		self
+	}
 }
 
 #[cfg(feature = "openapi")]
@@ -212,7 +274,7 @@ impl<R: IntoResponse + ResponseSchema> private::Sealed for R {}
 #[cfg(feature = "openapi")]
 impl<R: IntoResponse + ResponseSchema> IntoResponseWithSchema for R {}
 
-/// The default json returned on an 500 Internal Server Error.
+/// The default json returned on an error, e.g. a _500 Internal Server Error_.
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "openapi", derive(OpenapiType))]
 pub(crate) struct ResourceError {
@@ -220,14 +282,25 @@ pub(crate) struct ResourceError {
 	/// HTTP status code.
 	error: bool,
 	/// The error message.
-	message: String
+	message: String,
+	/// A stable, machine-readable error code, if available.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	code: Option<&'static str>
+}
+
+impl ResourceError {
+	pub(crate) fn with_code(mut self, code: &'static str) -> Self {
+		self.code = Some(code);
+		self
+	}
 }
 
 impl<T: ToString> From<T> for ResourceError {
 	fn from(message: T) -> Self {
 		Self {
 			error: true,
-			message: message.to_string()
+			message: message.to_string(),
+			code: None
 		}
 	}
 }
@@ -284,6 +357,14 @@ where
 	fn schema(code: StatusCode) -> OpenapiSchema {
 		Res::schema(code)
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		Res::headers(code)
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		Res::description(code)
+	}
 }
 
 #[cfg(test)]
@@ -294,12 +375,14 @@ mod test {
 
 	#[derive(Debug, Default, Deserialize, Serialize)]
 	#[cfg_attr(feature = "openapi", derive(openapi_type::OpenapiType))]
+	#[allow(dead_code)]
 	struct Msg {
 		msg: String
 	}
 
 	#[derive(Debug, Default, Error)]
 	#[error("An Error")]
+	#[allow(dead_code)]
 	struct MsgError;
 
 	#[test]
@@ -1,7 +1,7 @@
 use super::{handle_error, IntoResponse};
-#[cfg(feature = "openapi")]
-use crate::ResponseSchema;
 use crate::{IntoResponseError, Response};
+#[cfg(feature = "openapi")]
+use crate::{ResponseHeader, ResponseSchema};
 use futures_util::{future, future::FutureExt};
 #[cfg(feature = "openapi")]
 use gotham::hyper::StatusCode;
@@ -120,6 +120,24 @@ where
 			code => E::schema(code)
 		}
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		match code {
+			StatusCode::NO_CONTENT => {
+				<NoContent as ResponseSchema>::headers(StatusCode::NO_CONTENT)
+			},
+			code => E::headers(code)
+		}
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		match code {
+			StatusCode::NO_CONTENT => {
+				<NoContent as ResponseSchema>::description(StatusCode::NO_CONTENT)
+			},
+			code => E::description(code)
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use super::{handle_error, IntoResponse, IntoResponseError};
 use crate::{types::ResourceType, FromBody, RequestBody, Response};
 #[cfg(feature = "openapi")]
-use crate::{IntoResponseWithSchema, ResponseSchema};
+use crate::{IntoResponseWithSchema, ResponseHeader, ResponseSchema};
 use futures_core::future::Future;
 use futures_util::{future, future::FutureExt};
 use gotham::{
@@ -152,6 +152,20 @@ where
 			code => E::schema(code)
 		}
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		match code {
+			StatusCode::OK => <Raw<T> as ResponseSchema>::headers(StatusCode::OK),
+			code => E::headers(code)
+		}
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		match code {
+			StatusCode::OK => <Raw<T> as ResponseSchema>::description(StatusCode::OK),
+			code => E::description(code)
+		}
+	}
 }
 
 #[cfg(test)]
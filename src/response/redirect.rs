@@ -1,7 +1,7 @@
 use super::{handle_error, IntoResponse};
 use crate::{IntoResponseError, Response};
 #[cfg(feature = "openapi")]
-use crate::{NoContent, ResponseSchema};
+use crate::{NoContent, ResponseHeader, ResponseSchema};
 use futures_util::future::{BoxFuture, FutureExt, TryFutureExt};
 use gotham::hyper::{
 	header::{InvalidHeaderValue, LOCATION},
@@ -62,6 +62,13 @@ impl ResponseSchema for Redirect {
 		assert_eq!(code, StatusCode::SEE_OTHER);
 		<NoContent as ResponseSchema>::schema(StatusCode::NO_CONTENT)
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		assert_eq!(code, StatusCode::SEE_OTHER);
+		vec![ResponseHeader::new(LOCATION)
+			.with_description("The location to redirect to.")
+			.required()]
+	}
 }
 
 // private type due to parent mod
@@ -107,6 +114,22 @@ where
 			code => E::schema(code)
 		}
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		match code {
+			StatusCode::SEE_OTHER => <Redirect as ResponseSchema>::headers(StatusCode::SEE_OTHER),
+			code => E::headers(code)
+		}
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		match code {
+			StatusCode::SEE_OTHER => {
+				<Redirect as ResponseSchema>::description(StatusCode::SEE_OTHER)
+			},
+			code => E::description(code)
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1,7 +1,7 @@
 use super::{handle_error, IntoResponse, ResourceError};
-#[cfg(feature = "openapi")]
-use crate::ResponseSchema;
 use crate::{Response, ResponseBody, Success};
+#[cfg(feature = "openapi")]
+use crate::{ResponseHeader, ResponseSchema};
 use futures_core::future::Future;
 use gotham::{
 	anyhow::Error,
@@ -22,6 +22,18 @@ pub trait IntoResponseError {
 
 	#[cfg(feature = "openapi")]
 	fn schema(code: StatusCode) -> OpenapiSchema;
+
+	/// See [ResponseSchema::headers].
+	#[cfg(feature = "openapi")]
+	fn headers(_code: StatusCode) -> Vec<ResponseHeader> {
+		Vec::new()
+	}
+
+	/// See [ResponseSchema::description].
+	#[cfg(feature = "openapi")]
+	fn description(_code: StatusCode) -> Option<String> {
+		None
+	}
 }
 
 impl<E> IntoResponseError for E
@@ -88,6 +100,20 @@ where
 			code => E::schema(code)
 		}
 	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		match code {
+			StatusCode::OK => Vec::new(),
+			code => E::headers(code)
+		}
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		match code {
+			StatusCode::OK => None,
+			code => E::description(code)
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1,9 +1,17 @@
+#[cfg(feature = "cors")]
+use crate::cors::{CorsConfig, CorsPreflightHandler};
+#[cfg(feature = "validate-responses")]
+use crate::openapi::response_validation::{validate_response, ResponseValidation};
 #[cfg(feature = "openapi")]
 use crate::openapi::{
 	builder::{OpenapiBuilder, OpenapiInfo},
-	router::OpenapiRouter
+	router::OpenapiRouter,
+	validation::{validate_request, RequestValidation}
+};
+use crate::{
+	response::ResourceError, Endpoint, FromBody, IntoResponse, RequestBody, Resource, Response
 };
-use crate::{response::ResourceError, Endpoint, FromBody, IntoResponse, Resource, Response};
+use futures_util::future::FutureExt;
 #[cfg(feature = "cors")]
 use gotham::router::route::matcher::AccessControlRequestMethodMatcher;
 use gotham::{
@@ -14,7 +22,7 @@ use gotham::{
 	pipeline::PipelineHandleChain,
 	prelude::*,
 	router::{
-		builder::{RouterBuilder, ScopeBuilder},
+		builder::{DefineSingleRoute, RouterBuilder, ScopeBuilder},
 		route::matcher::{AcceptHeaderRouteMatcher, ContentTypeHeaderRouteMatcher, RouteMatcher},
 		RouteNonMatch
 	},
@@ -23,9 +31,15 @@ use gotham::{
 #[cfg(feature = "openapi")]
 use openapi_type::OpenapiType;
 use std::{any::TypeId, panic::RefUnwindSafe};
+#[cfg(feature = "cors")]
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock}
+};
 
 /// Allow us to extract an id from a path.
 #[derive(Clone, Copy, Debug, Deserialize, StateData, StaticResponseExtender)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 #[cfg_attr(feature = "openapi", derive(OpenapiType))]
 pub struct PathExtractor<ID: RefUnwindSafe + Send + 'static> {
 	pub id: ID
@@ -57,6 +71,61 @@ pub trait DrawResourceRoutes {
 	#[openapi_bound(E: crate::EndpointWithSchema)]
 	#[non_openapi_bound(E: crate::Endpoint)]
 	fn endpoint<E: 'static>(&mut self);
+
+	/// Use `cors` for all endpoints of this resource that are registered afterwards, overriding
+	/// any [CorsConfig](crate::CorsConfig) in the state.
+	#[cfg(feature = "cors")]
+	#[allow(unused_variables)]
+	fn cors_config(&mut self, cors: crate::CorsConfig) {}
+
+	/// Use `name` as the OpenAPI tag for all endpoints of this resource that are registered
+	/// afterwards, and add it to the specification together with its `description`.
+	#[openapi_only]
+	#[allow(unused_variables)]
+	fn tag(&mut self, name: &str, description: Option<&str>) {}
+}
+
+/// Information about the resource whose routes are being drawn.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct ResourceContext {
+	/// The methods of all endpoints registered so far for each path of the resource. This is
+	/// used to answer CORS preflight requests.
+	#[cfg(feature = "cors")]
+	methods: HashMap<String, Arc<RwLock<Vec<Method>>>>,
+	/// The CORS configuration of the resource, if any.
+	#[cfg(feature = "cors")]
+	cors: Option<CorsConfig>,
+	/// The OpenAPI tag of the resource, if any.
+	#[cfg(feature = "openapi")]
+	pub(crate) tag: Option<String>,
+	/// The description of the OpenAPI tag of the resource, if any.
+	#[cfg(feature = "openapi")]
+	pub(crate) tag_description: Option<String>,
+	/// The validator of the endpoint that is being registered, if request validation is enabled.
+	#[cfg(feature = "openapi")]
+	pub(crate) validation: Option<RequestValidation>,
+	/// The response validator of the endpoint that is being registered, if response validation
+	/// is enabled.
+	#[cfg(feature = "validate-responses")]
+	pub(crate) response_validation: Option<ResponseValidation>
+}
+
+impl ResourceContext {
+	/// Add `method` to the methods of `uri`. Returns the shared list of methods of `uri`, or
+	/// `None` if `method` was already registered for `uri`.
+	#[cfg(feature = "cors")]
+	fn add(&mut self, uri: &str, method: Method) -> Option<Arc<RwLock<Vec<Method>>>> {
+		let methods = self.methods.entry(uri.to_owned()).or_default();
+		{
+			let mut methods = methods.write().unwrap();
+			if methods.contains(&method) {
+				return None;
+			}
+			methods.push(method);
+		}
+		Some(Arc::clone(methods))
+	}
 }
 
 fn response_from(res: Response, state: &State) -> gotham::hyper::Response<Body> {
@@ -94,6 +163,11 @@ where
 	<E::Output as IntoResponse>::Err: Into<HandlerError>
 {
 	trace!("entering endpoint_handler");
+	#[cfg(feature = "auth")]
+	if let Some(res) = crate::csrf::handle_csrf(state) {
+		return Ok(res);
+	}
+
 	let placeholders = E::Placeholders::take_from(state);
 	// workaround for E::Placeholders and E::Param being the same type
 	// when fixed remove `Clone` requirement on endpoint
@@ -115,6 +189,11 @@ where
 				}
 			};
 
+			#[cfg(feature = "openapi")]
+			if let Some(res) = validate_request(state, Some((&body, &content_type)))? {
+				return Ok(response_from(res, state));
+			}
+
 			match E::Body::from_body(body, content_type) {
 				Ok(body) => Some(body),
 				Err(e) => {
@@ -127,15 +206,114 @@ where
 				}
 			}
 		},
-		false => None
+		false => {
+			#[cfg(feature = "openapi")]
+			if let Some(res) = validate_request(state, None)? {
+				return Ok(response_from(res, state));
+			}
+			None
+		}
 	};
 
 	let out = E::handle(state, placeholders, params, body).await;
 	let res = out.into_response().await.map_err(Into::into)?;
+	#[cfg(feature = "validate-responses")]
+	let res = validate_response(state, res).await?;
 	debug!("Returning response {res:?}");
 	Ok(response_from(res, state))
 }
 
+/// Data of the resource that is put into the state before calling the handler of an endpoint.
+#[derive(Clone, Default)]
+struct EndpointStateData {
+	#[cfg(feature = "cors")]
+	cors: Option<CorsConfig>,
+	#[cfg(feature = "openapi")]
+	validation: Option<RequestValidation>,
+	#[cfg(feature = "validate-responses")]
+	response_validation: Option<ResponseValidation>
+}
+
+impl EndpointStateData {
+	#[cfg_attr(
+		not(any(feature = "cors", feature = "openapi")),
+		allow(unused_variables)
+	)]
+	fn new(ctx: &ResourceContext) -> Self {
+		Self {
+			#[cfg(feature = "cors")]
+			cors: ctx.cors.clone(),
+			#[cfg(feature = "openapi")]
+			validation: ctx.validation.clone(),
+			#[cfg(feature = "validate-responses")]
+			response_validation: ctx.response_validation.clone()
+		}
+	}
+
+	fn is_empty(&self) -> bool {
+		#[allow(unused_mut)]
+		let mut empty = true;
+		#[cfg(feature = "cors")]
+		{
+			empty &= self.cors.is_none();
+		}
+		#[cfg(feature = "openapi")]
+		{
+			empty &= self.validation.is_none();
+		}
+		#[cfg(feature = "validate-responses")]
+		{
+			empty &= self.response_validation.is_none();
+		}
+		empty
+	}
+
+	#[cfg_attr(
+		not(any(feature = "cors", feature = "openapi")),
+		allow(unused_variables)
+	)]
+	fn put_into(self, state: &mut State) {
+		#[cfg(feature = "cors")]
+		if let Some(cors) = self.cors {
+			state.put(cors);
+		}
+		#[cfg(feature = "openapi")]
+		if let Some(validation) = self.validation {
+			state.put(validation);
+		}
+		#[cfg(feature = "validate-responses")]
+		if let Some(validation) = self.response_validation {
+			state.put(validation);
+		}
+	}
+}
+
+/// Direct `route` to the handler of the endpoint `E`. If the resource has a CORS configuration
+/// or validates requests or responses, these are put into the state before calling the handler.
+fn to_endpoint_handler<E, R>(route: R, ctx: &ResourceContext)
+where
+	E: Endpoint + 'static,
+	R: DefineSingleRoute
+{
+	let data = EndpointStateData::new(ctx);
+	if data.is_empty() {
+		return route.to_async_borrowing(endpoint_handler::<E>);
+	}
+	route.to_new_handler(move || {
+		let data = data.clone();
+		Ok(move |mut state: State| {
+			data.put_into(&mut state);
+			async move {
+				match endpoint_handler::<E>(&mut state).await {
+					Ok(res) => Ok((state, res)),
+					Err(err) => Err((state, err))
+				}
+			}
+			.boxed()
+		})
+	});
+}
+
 #[derive(Clone)]
 struct MaybeMatchAcceptHeader {
 	matcher: Option<AcceptHeaderRouteMatcher>
@@ -226,11 +404,11 @@ macro_rules! implDrawResourceRoutes {
 				if path.starts_with('/') {
 					path = &path[1..];
 				}
-				R::setup((self, path));
+				R::setup((self, path, &mut ResourceContext::default()));
 			}
 		}
 
-		impl<'a, C, P> DrawResourceRoutes for (&mut $implType<'a, C, P>, &str)
+		impl<'a, C, P> DrawResourceRoutes for (&mut $implType<'a, C, P>, &str, &mut ResourceContext)
 		where
 			C: PipelineHandleChain<P> + Copy + Send + Sync + 'static,
 			P: RefUnwindSafe + Send + Sync + 'static
@@ -238,25 +416,37 @@ macro_rules! implDrawResourceRoutes {
 			fn endpoint<E: Endpoint + 'static>(&mut self) {
 				let uri = format!("{}/{}", self.1, E::uri());
 				debug!("Registering endpoint for {uri}");
+				#[cfg(feature = "cors")]
+				let methods = self.2.add(&uri, E::http_method());
+				let ctx = &*self.2;
 				self.0.associate(&uri, |assoc| {
-					assoc
+					let body_types = E::needs_body().then(E::Body::supported_types).flatten();
+					let route = assoc
 						.request(vec![E::http_method()])
 						.add_route_matcher(MaybeMatchAcceptHeader::new(E::Output::accepted_types()))
+						.add_route_matcher(MaybeMatchContentTypeHeader::new(body_types))
 						.with_path_extractor::<E::Placeholders>()
-						.with_query_string_extractor::<E::Params>()
-						.to_async_borrowing(endpoint_handler::<E>);
+						.with_query_string_extractor::<E::Params>();
+					to_endpoint_handler::<E, _>(route, ctx);
 
+					// the preflight handler decides based on the CORS configuration of the resource or
+					// the state whether the preflight request is allowed
 					#[cfg(feature = "cors")]
-					if E::http_method() != Method::GET {
+					if let Some(methods) = methods {
 						assoc
 							.options()
 							.add_route_matcher(AccessControlRequestMethodMatcher::new(
 								E::http_method()
 							))
-							.to(crate::cors::cors_preflight_handler);
+							.to_new_handler(CorsPreflightHandler::new(methods, ctx.cors.clone()));
 					}
 				});
 			}
+
+			#[cfg(feature = "cors")]
+			fn cors_config(&mut self, cors: CorsConfig) {
+				self.2.cors = Some(cors);
+			}
 		}
 	};
 }
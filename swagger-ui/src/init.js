@@ -0,0 +1,10 @@
+window.addEventListener('load', function() {
+	var spec = JSON.parse(document.getElementById('spec').textContent);
+	window.ui = SwaggerUIBundle({
+		spec: spec,
+		dom_id: '#swagger-ui',
+		deepLinking: true,
+		presets: [SwaggerUIBundle.presets.apis],
+		layout: 'BaseLayout'
+	});
+});
@@ -0,0 +1,112 @@
+#![forbid(elided_lifetimes_in_paths, unsafe_code)]
+
+//! Private implementation detail of the `gotham_restful` crate.
+
+use base64::prelude::*;
+use either::Either;
+use sha2::{Digest, Sha256};
+use std::{io::Write, iter};
+
+const BUNDLE: &str = include_str!("swagger-ui-bundle.js");
+const INIT: &str = include_str!("init.js");
+const STYLE: &str = concat!(include_str!("swagger-ui.css"), "\nbody{margin:0}");
+
+fn hash(content: &str) -> String {
+	let mut hash = Sha256::new();
+	hash.update(content);
+	BASE64_STANDARD.encode(hash.finalize())
+}
+
+#[doc(hidden)]
+pub struct SwaggerUi {
+	/// HTML code.
+	pub html: Vec<u8>,
+
+	/// Hashes of the JS and CSS, base64 encoded.
+	bundle_hash: String,
+	init_hash: String,
+	style_hash: String,
+
+	/// Absolute server urls that "Try it out" needs to connect to.
+	connect_src: Vec<String>
+}
+
+impl SwaggerUi {
+	/// Return the value that should be set for the `Content-Security-Policy` header.
+	pub fn csp(&self) -> String {
+		let mut connect_src = String::from("'self'");
+		for url in &self.connect_src {
+			connect_src.push(' ');
+			connect_src.push_str(url);
+		}
+
+		format!(
+			concat!(
+				"default-src 'none';",
+				"base-uri 'none';",
+				"script-src 'unsafe-inline' 'sha256-{}' 'sha256-{}' 'strict-dynamic';",
+				"style-src 'sha256-{}';",
+				"connect-src {};",
+				"img-src data:;"
+			),
+			self.bundle_hash, self.init_hash, self.style_hash, connect_src
+		)
+	}
+}
+
+/// Create the HTML for the Swagger UI. The `servers` should contain the urls of all servers
+/// from the spec, so that "Try it out" is allowed to connect to them.
+#[doc(hidden)]
+pub fn html<'a, I>(spec: String, servers: I) -> SwaggerUi
+where
+	I: IntoIterator<Item = &'a str>
+{
+	let encoded_spec = spec
+		.chars()
+		.flat_map(|c| match c {
+			'&' => Either::Left("&amp;".chars()),
+			'<' => Either::Left("&lt;".chars()),
+			'>' => Either::Left("&gt;".chars()),
+			c => Either::Right(iter::once(c))
+		})
+		.collect::<String>();
+
+	// relative urls are covered by 'self', and templated urls cannot be expressed in the csp
+	let connect_src = servers
+		.into_iter()
+		.filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+		.filter(|url| !url.contains(['{', '}', ';', ',', ' ', '\'']))
+		.map(String::from)
+		.collect();
+
+	let mut html = Vec::<u8>::new();
+	write!(
+		html,
+		concat!(
+			"<!DOCTYPE HTML>",
+			"<html>",
+			"<head>",
+			r#"<meta charset="utf-8"/>"#,
+			r#"<meta name="viewport" content="width=device-width,initial-scale=1"/>"#,
+			"<style>{}</style>",
+			"</head>",
+			"<body>",
+			r#"<div id="spec" hidden>{}</div>"#,
+			r#"<div id="swagger-ui"></div>"#,
+			"<script>{}</script>",
+			"<script>{}</script>",
+			"</body>",
+			"</html>"
+		),
+		STYLE, encoded_spec, BUNDLE, INIT
+	)
+	.unwrap();
+
+	SwaggerUi {
+		html,
+		bundle_hash: hash(BUNDLE),
+		init_hash: hash(INIT),
+		style_hash: hash(STYLE),
+		connect_src
+	}
+}
@@ -1,15 +1,16 @@
 #![cfg(feature = "cors")]
 use gotham::{
-	hyper::{body::Body, client::connect::Connect, header::*, StatusCode},
+	hyper::{body::Body, client::connect::Connect, header::*, Method, StatusCode},
 	mime::TEXT_PLAIN,
 	pipeline::{new_pipeline, single_pipeline},
-	router::build_router,
+	router::{build_router, build_simple_router},
 	test::{Server, TestRequest, TestServer}
 };
 use gotham_restful::{
 	cors::{Headers, Origin},
 	read_all, update_all, CorsConfig, DrawResources, Raw, Resource
 };
+use std::sync::Arc;
 
 #[derive(Resource)]
 #[resource(read_all, update_all)]
@@ -102,7 +103,8 @@ fn test_preflight(
 		headers
 			.get(ACCESS_CONTROL_ALLOW_METHODS)
 			.and_then(|value| value.to_str().ok()),
-		Some(method)
+		// all methods registered for the path
+		Some("GET,PUT")
 	);
 	assert_eq!(
 		headers
@@ -173,6 +175,22 @@ fn test_preflight_headers(
 	);
 }
 
+fn test_preflight_status(server: &TestServer, headers: &[(HeaderName, &str)], status: StatusCode) {
+	let client = server.client();
+	let mut req = client
+		.options("http://example.org/foo")
+		.with_header(ORIGIN, "http://example.org".parse().unwrap());
+	for (name, value) in headers {
+		req = req.with_header(name.clone(), value.parse().unwrap());
+	}
+	let res = req.perform().unwrap();
+	assert_eq!(res.status(), status);
+	if status != StatusCode::NO_CONTENT {
+		assert!(!res.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+		assert!(!res.headers().contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+	}
+}
+
 #[test]
 fn cors_origin_none() {
 	let cfg = Default::default();
@@ -317,6 +335,22 @@ fn cors_headers_none() {
 	);
 }
 
+#[test]
+fn cors_no_config() {
+	let server = TestServer::new(build_simple_router(|router| {
+		router.resource::<FooResource>("/foo")
+	}))
+	.unwrap();
+
+	test_preflight_headers(
+		&server,
+		"PUT",
+		Some("content-type"),
+		None,
+		"access-control-request-method"
+	);
+}
+
 #[test]
 fn cors_headers_list() {
 	let cfg = CorsConfig {
@@ -432,3 +466,400 @@ fn cors_max_age() {
 		false
 	);
 }
+
+#[test]
+fn cors_origin_list() {
+	let cfg = CorsConfig {
+		origin: Origin::List(vec![
+			"https://foo.com".to_owned(),
+			"http://*.example.org".to_owned(),
+			"http://example.org".to_owned(),
+		]),
+		credentials: true,
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	test_preflight(
+		&server,
+		"PUT",
+		Some("http://example.org"),
+		"access-control-request-method,origin",
+		true,
+		0
+	);
+
+	test_response(
+		server.client().get("http://example.org/foo"),
+		Some("http://example.org"),
+		Some("origin"),
+		true
+	);
+}
+
+fn test_preflight_rejected(server: &TestServer, origin: &str) {
+	let res = server
+		.client()
+		.options("http://example.org/foo")
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "PUT".parse().unwrap())
+		.with_header(ORIGIN, origin.parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	let headers = res.headers();
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_METHODS));
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_HEADERS));
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+	assert!(!headers.contains_key(ACCESS_CONTROL_MAX_AGE));
+	assert_eq!(
+		headers.get(VARY).and_then(|value| value.to_str().ok()),
+		Some("access-control-request-method,origin")
+	);
+}
+
+#[test]
+fn cors_origin_list_rejected() {
+	let cfg = CorsConfig {
+		origin: Origin::List(vec!["https://*.example.org".to_owned()]),
+		headers: Headers::List(vec![CONTENT_TYPE]),
+		credentials: true,
+		max_age: 3600,
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	test_preflight_rejected(&server, "https://example.org");
+	test_preflight_rejected(&server, "https://evil.org");
+
+	let res = server
+		.client()
+		.get("http://example.org/foo")
+		.with_header(ORIGIN, "https://evil.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	let headers = res.headers();
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+	assert!(!headers.contains_key(ACCESS_CONTROL_ALLOW_CREDENTIALS));
+	assert_eq!(
+		headers.get(VARY).and_then(|value| value.to_str().ok()),
+		Some("origin")
+	);
+}
+
+#[test]
+fn cors_origin_predicate() {
+	let cfg = CorsConfig {
+		origin: Origin::Predicate(Arc::new(|origin| origin.ends_with(".org"))),
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	test_preflight(
+		&server,
+		"PUT",
+		Some("http://example.org"),
+		"access-control-request-method,origin",
+		false,
+		0
+	);
+	test_preflight_rejected(&server, "https://example.com");
+}
+
+#[test]
+fn cors_methods() {
+	let cfg = CorsConfig {
+		methods: Some(vec![Method::PUT]),
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	let res = server
+		.client()
+		.options("http://example.org/foo")
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "PUT".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	assert_eq!(
+		res.headers()
+			.get(ACCESS_CONTROL_ALLOW_METHODS)
+			.and_then(|value| value.to_str().ok()),
+		Some("PUT")
+	);
+
+	let server = test_server(CorsConfig {
+		methods: Some(vec![Method::GET]),
+		..Default::default()
+	});
+	test_preflight_status(
+		&server,
+		&[(ACCESS_CONTROL_REQUEST_METHOD, "PUT")],
+		StatusCode::FORBIDDEN
+	);
+}
+
+#[test]
+fn cors_expose_headers() {
+	let cfg = CorsConfig {
+		origin: Origin::Star,
+		expose_headers: vec![ETAG, LINK],
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	let res = server
+		.client()
+		.get("http://example.org/foo")
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	assert_eq!(
+		res.headers()
+			.get(ACCESS_CONTROL_EXPOSE_HEADERS)
+			.and_then(|value| value.to_str().ok()),
+		Some("etag,link")
+	);
+
+	let res = server
+		.client()
+		.options("http://example.org/foo")
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "PUT".parse().unwrap())
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert!(!res.headers().contains_key(ACCESS_CONTROL_EXPOSE_HEADERS));
+}
+
+#[derive(Resource)]
+#[resource(read_all, update_all, cors = "authorization_cors")]
+struct AuthorizationResource;
+
+fn authorization_cors() -> CorsConfig {
+	CorsConfig {
+		origin: Origin::Star,
+		headers: Headers::List(vec![AUTHORIZATION]),
+		..Default::default()
+	}
+}
+
+#[test]
+fn cors_preflight_get() {
+	let server = TestServer::new(build_simple_router(|router| {
+		router.resource::<AuthorizationResource>("/foo")
+	}))
+	.unwrap();
+
+	test_preflight_headers(
+		&server,
+		"GET",
+		Some("authorization"),
+		Some("authorization"),
+		"access-control-request-method"
+	);
+	test_preflight_headers(
+		&server,
+		"PUT",
+		Some("authorization"),
+		Some("authorization"),
+		"access-control-request-method"
+	);
+}
+
+#[test]
+fn cors_preflight_routes() {
+	let server = test_server(CorsConfig {
+		origin: Origin::Star,
+		..Default::default()
+	});
+
+	test_preflight_status(
+		&server,
+		&[(ACCESS_CONTROL_REQUEST_METHOD, "GET")],
+		StatusCode::NO_CONTENT
+	);
+	test_preflight_status(
+		&server,
+		&[(ACCESS_CONTROL_REQUEST_METHOD, "PUT")],
+		StatusCode::NO_CONTENT
+	);
+
+	// requests that are no preflight requests are not answered by the preflight route
+	test_preflight_status(&server, &[], StatusCode::NOT_FOUND);
+	test_preflight_status(
+		&server,
+		&[(ACCESS_CONTROL_REQUEST_METHOD, "DELETE")],
+		StatusCode::NOT_FOUND
+	);
+}
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct ReadOnlyResource;
+
+#[test]
+fn cors_preflight_get_pipeline_config() {
+	let cfg = CorsConfig {
+		origin: Origin::Star,
+		headers: Headers::List(vec![AUTHORIZATION]),
+		..Default::default()
+	};
+	let (chain, pipeline) = single_pipeline(new_pipeline().add(cfg).build());
+	let server = TestServer::new(build_router(chain, pipeline, |router| {
+		router.resource::<ReadOnlyResource>("/read-only");
+	}))
+	.unwrap();
+
+	let res = server
+		.client()
+		.options("http://example.org/read-only")
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "GET".parse().unwrap())
+		.with_header(
+			ACCESS_CONTROL_REQUEST_HEADERS,
+			"authorization".parse().unwrap()
+		)
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	let headers = res.headers();
+	assert_eq!(headers[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+	assert_eq!(headers[ACCESS_CONTROL_ALLOW_METHODS], "GET");
+	assert_eq!(headers[ACCESS_CONTROL_ALLOW_HEADERS], "authorization");
+}
+
+#[derive(Resource)]
+#[resource(read_all, cors = "public_cors")]
+struct PublicResource;
+
+fn public_cors() -> CorsConfig {
+	CorsConfig {
+		origin: Origin::Star,
+		methods: Some(vec![Method::GET]),
+		..Default::default()
+	}
+}
+
+#[test]
+fn cors_resource_config() {
+	let cfg = CorsConfig {
+		origin: Origin::Single("https://foo.com".to_owned()),
+		..Default::default()
+	};
+	let (chain, pipeline) = single_pipeline(new_pipeline().add(cfg).build());
+	let server = TestServer::new(build_router(chain, pipeline, |router| {
+		router.resource::<FooResource>("/foo");
+		router.resource::<PublicResource>("/public");
+	}))
+	.unwrap();
+
+	test_response(
+		server.client().get("http://example.org/foo"),
+		Some("https://foo.com"),
+		None,
+		false
+	);
+	test_response(
+		server.client().get("http://example.org/public"),
+		Some("*"),
+		None,
+		false
+	);
+
+	let res = server
+		.client()
+		.options("http://example.org/public")
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "GET".parse().unwrap())
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	let headers = res.headers();
+	assert_eq!(
+		headers
+			.get(ACCESS_CONTROL_ALLOW_ORIGIN)
+			.and_then(|value| value.to_str().ok()),
+		Some("*")
+	);
+	assert_eq!(
+		headers
+			.get(ACCESS_CONTROL_ALLOW_METHODS)
+			.and_then(|value| value.to_str().ok()),
+		Some("GET")
+	);
+}
+
+#[test]
+fn cors_preflight_validation() {
+	let cfg = CorsConfig {
+		origin: Origin::Star,
+		headers: Headers::List(vec![CONTENT_TYPE]),
+		methods: Some(vec![Method::GET, Method::PUT]),
+		..Default::default()
+	};
+	let server = test_server(cfg);
+
+	test_preflight_status(
+		&server,
+		&[(ACCESS_CONTROL_REQUEST_METHOD, "not a method")],
+		StatusCode::NOT_FOUND
+	);
+	test_preflight_status(
+		&server,
+		&[
+			(ACCESS_CONTROL_REQUEST_METHOD, "PUT"),
+			(ACCESS_CONTROL_REQUEST_HEADERS, "content-type,x-custom")
+		],
+		StatusCode::FORBIDDEN
+	);
+	test_preflight_status(
+		&server,
+		&[
+			(ACCESS_CONTROL_REQUEST_METHOD, "PUT"),
+			(ACCESS_CONTROL_REQUEST_HEADERS, "Content-Type")
+		],
+		StatusCode::NO_CONTENT
+	);
+}
+
+#[test]
+fn cors_private_network() {
+	let private_network = HeaderName::from_static("access-control-request-private-network");
+	let allow_private_network = HeaderName::from_static("access-control-allow-private-network");
+
+	let server = test_server(CorsConfig {
+		origin: Origin::Star,
+		..Default::default()
+	});
+	test_preflight_status(
+		&server,
+		&[
+			(ACCESS_CONTROL_REQUEST_METHOD, "PUT"),
+			(private_network.clone(), "true")
+		],
+		StatusCode::FORBIDDEN
+	);
+
+	let server = test_server(CorsConfig {
+		origin: Origin::Star,
+		allow_private_network: true,
+		..Default::default()
+	});
+	let res = server
+		.client()
+		.options("http://example.org/foo")
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.with_header(ACCESS_CONTROL_REQUEST_METHOD, "PUT".parse().unwrap())
+		.with_header(private_network, "true".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	assert_eq!(
+		res.headers()
+			.get(allow_private_network)
+			.and_then(|value| value.to_str().ok()),
+		Some("true")
+	);
+}
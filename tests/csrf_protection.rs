@@ -0,0 +1,153 @@
+#![cfg(feature = "auth")]
+use gotham::{
+	hyper::{body::Body, header::*, StatusCode},
+	mime::TEXT_PLAIN,
+	pipeline::{new_pipeline, single_pipeline},
+	router::build_router,
+	test::TestServer
+};
+use gotham_restful::{create, read_all, CsrfConfig, DrawResources, Raw, Resource};
+
+#[derive(Resource)]
+#[resource(read_all, create)]
+struct FooResource;
+
+#[read_all]
+fn read_all() {}
+
+#[create]
+fn create(_body: Raw<Vec<u8>>) {}
+
+fn test_server(cfg: CsrfConfig) -> TestServer {
+	let (chain, pipeline) = single_pipeline(new_pipeline().add(cfg).build());
+	TestServer::new(build_router(chain, pipeline, |router| {
+		router.resource::<FooResource>("/foo")
+	}))
+	.unwrap()
+}
+
+fn double_submit() -> TestServer {
+	test_server(CsrfConfig::DoubleSubmitCookie {
+		cookie: "csrf".to_owned(),
+		header: HeaderName::from_static("x-csrf-token")
+	})
+}
+
+#[test]
+fn csrf_safe_method() {
+	let server = double_submit();
+	let res = server
+		.client()
+		.get("http://example.org/foo")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+}
+
+#[test]
+fn csrf_double_submit_valid() {
+	let server = double_submit();
+	let res = server
+		.client()
+		.post("http://example.org/foo", Body::empty(), TEXT_PLAIN)
+		.with_header(COOKIE, "csrf=wdPE6PZp0Xb2".parse().unwrap())
+		.with_header("x-csrf-token", "wdPE6PZp0Xb2".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+}
+
+#[test]
+fn csrf_double_submit_invalid() {
+	let server = double_submit();
+	let res = server
+		.client()
+		.post("http://example.org/foo", Body::empty(), TEXT_PLAIN)
+		.with_header(COOKIE, "csrf=wdPE6PZp0Xb2".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::FORBIDDEN);
+	assert_eq!(
+		res.headers()
+			.get(CONTENT_TYPE)
+			.and_then(|value| value.to_str().ok()),
+		Some("application/json")
+	);
+	let body = res.read_body().unwrap();
+	assert_eq!(
+		body.as_slice(),
+		br#"{"error":true,"message":"Missing CSRF header"}"#
+	);
+}
+
+#[test]
+fn csrf_same_origin() {
+	let server = test_server(CsrfConfig::SameOrigin {
+		trusted_origins: Vec::new()
+	});
+
+	let res = server
+		.client()
+		.post("http://example.org/foo", Body::empty(), TEXT_PLAIN)
+		.with_header(ORIGIN, "https://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+	let res = server
+		.client()
+		.post("http://example.org/foo", Body::empty(), TEXT_PLAIN)
+		.with_header(ORIGIN, "http://example.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+	let res = server
+		.client()
+		.post("http://example.org/foo", Body::empty(), TEXT_PLAIN)
+		.with_header(ORIGIN, "http://evil.org".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[cfg(feature = "openapi")]
+fn csrf_parameter(cfg: CsrfConfig) -> serde_json::Value {
+	use gotham_restful::{DrawResourcesWithSchema, OpenapiInfo, OpenapiSpec};
+
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let spec = OpenapiSpec::new(info, |mut router| {
+		router.resource::<FooResource>("foo");
+	})
+	.with_csrf_config(cfg);
+	let openapi: serde_json::Value = serde_json::from_str(&spec.to_json().unwrap()).unwrap();
+	assert!(openapi["paths"]["/foo"]["get"]["parameters"].is_null());
+	openapi["paths"]["/foo"]["post"]["parameters"][0].clone()
+}
+
+#[test]
+#[cfg(feature = "openapi")]
+fn csrf_openapi() {
+	let param = csrf_parameter(CsrfConfig::DoubleSubmitCookie {
+		cookie: "csrf".to_owned(),
+		header: HeaderName::from_static("x-csrf-token")
+	});
+	assert_eq!(param["in"], "header");
+	assert_eq!(param["name"], "x-csrf-token");
+	assert_eq!(param["required"], true);
+
+	let param = csrf_parameter(CsrfConfig::SameOrigin {
+		trusted_origins: vec!["https://trusted.org".to_owned()]
+	});
+	assert_eq!(param["in"], "header");
+	assert_eq!(param["name"], "origin");
+	assert_ne!(param["required"], true);
+	assert!(param["description"]
+		.as_str()
+		.unwrap()
+		.contains("`https://trusted.org`"));
+}
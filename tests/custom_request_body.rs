@@ -1,6 +1,6 @@
 use gotham::{
-	hyper::header::CONTENT_TYPE,
-	mime::{Mime, TEXT_PLAIN},
+	hyper::{header::CONTENT_TYPE, Method, StatusCode},
+	mime::{Mime, APPLICATION_JSON, TEXT_PLAIN},
 	router::builder::*,
 	test::TestServer
 };
@@ -24,12 +24,16 @@ fn create(body: Foo) -> Raw<Vec<u8>> {
 	Raw::new(body.content, body.content_type)
 }
 
-#[test]
-fn custom_request_body() {
-	let server = TestServer::new(build_simple_router(|router| {
+fn server() -> TestServer {
+	TestServer::new(build_simple_router(|router| {
 		router.resource::<FooResource>("foo");
 	}))
-	.unwrap();
+	.unwrap()
+}
+
+#[test]
+fn custom_request_body() {
+	let server = server();
 
 	let res = server
 		.client()
@@ -44,3 +48,23 @@ fn custom_request_body() {
 	let body: &[u8] = res.as_ref();
 	assert_eq!(body, RESPONSE);
 }
+
+#[test]
+fn unsupported_content_type() {
+	let res = server()
+		.client()
+		.post("http://localhost/foo", RESPONSE, APPLICATION_JSON)
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[test]
+fn missing_content_type() {
+	let res = server()
+		.client()
+		.build_request(Method::POST, "http://localhost/foo")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
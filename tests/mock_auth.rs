@@ -0,0 +1,84 @@
+#![cfg(feature = "test-util")]
+use gotham::{
+	hyper::StatusCode,
+	mime::TEXT_PLAIN,
+	pipeline::{new_pipeline, single_pipeline},
+	router::build_router,
+	test::TestServer
+};
+use gotham_restful::{
+	read_all, AuthStatus, AuthSuccess, DrawResources, MockAuthMiddleware, Raw, Resource
+};
+use serde::Deserialize;
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+#[derive(Clone, Deserialize)]
+struct AuthData {
+	sub: String
+}
+
+#[read_all]
+fn read_all(auth: AuthStatus<AuthData>) -> AuthSuccess<Raw<String>> {
+	Ok(Raw::new(auth.ok()?.sub, TEXT_PLAIN))
+}
+
+fn test_server(auth: MockAuthMiddleware<AuthData>) -> TestServer {
+	let (chain, pipeline) = single_pipeline(new_pipeline().add(auth).build());
+	TestServer::new(build_router(chain, pipeline, |router| {
+		router.resource::<FooResource>("/foo")
+	}))
+	.unwrap()
+}
+
+#[test]
+fn mock_auth_header() {
+	let server = test_server(MockAuthMiddleware::from_header("x-mock-auth"));
+
+	let res = server
+		.client()
+		.get("http://localhost/foo")
+		.with_header("x-mock-auth", r#"{"sub":"msrd0"}"#.parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(res.read_body().unwrap(), b"msrd0");
+
+	let res = server
+		.client()
+		.get("http://localhost/foo")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::FORBIDDEN);
+	assert_eq!(
+		res.read_body().unwrap(),
+		br#"{"error":true,"message":"Missing token","code":"missing_token"}"#
+	);
+
+	let res = server
+		.client()
+		.get("http://localhost/foo")
+		.with_header("x-mock-auth", "invalid".parse().unwrap())
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[test]
+fn mock_auth_closure() {
+	let server = test_server(MockAuthMiddleware::new(|_| {
+		AuthStatus::Authenticated(AuthData {
+			sub: "msrd0".to_owned()
+		})
+	}));
+
+	let res = server
+		.client()
+		.get("http://localhost/foo")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(res.read_body().unwrap(), b"msrd0");
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "openapi")]
+
+use gotham::{hyper::StatusCode, router::build_simple_router, test::TestServer};
+use gotham_restful::*;
+use serde_json::Value;
+
+#[derive(Resource)]
+#[resource(read_all, read)]
+struct PublicResource;
+
+#[read_all]
+fn read_all() {}
+
+#[read(hidden)]
+fn read(_id: u64) {}
+
+#[derive(Resource)]
+#[resource(ban)]
+struct AdminResource;
+
+#[endpoint(method = "gotham::hyper::Method::POST", uri = "ban")]
+fn ban() {}
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct SharedResource;
+
+/// Only used internally.
+#[derive(Resource)]
+#[resource(internal)]
+struct InternalResource;
+
+#[endpoint(method = "gotham::hyper::Method::GET", uri = "internal", hidden)]
+fn internal() {}
+
+fn info(title: &str) -> OpenapiInfo {
+	OpenapiInfo {
+		title: title.to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	}
+}
+
+fn server() -> TestServer {
+	TestServer::new(build_simple_router(|router| {
+		router.with_openapi(info("Public"), |mut router| {
+			router.add_document("admin", info("Admin"));
+			router.resource::<PublicResource>("public");
+			router.in_documents(&["admin"], |router| {
+				router.resource::<AdminResource>("admin");
+				router.openapi_spec("admin/openapi");
+			});
+			router.in_documents(&[DEFAULT_OPENAPI_DOCUMENT, "admin"], |router| {
+				router.resource::<SharedResource>("shared");
+			});
+			router.resource::<InternalResource>("internal");
+			router.openapi_spec("openapi");
+		});
+	}))
+	.unwrap()
+}
+
+fn openapi(server: &TestServer, path: &str) -> Value {
+	let body = server
+		.client()
+		.get(format!("http://localhost/{path}"))
+		.perform()
+		.unwrap()
+		.read_body()
+		.unwrap();
+	serde_json::from_slice(&body).unwrap()
+}
+
+fn tags(openapi: &Value) -> Vec<&str> {
+	let mut tags = openapi["tags"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|tag| tag["name"].as_str().unwrap())
+		.collect::<Vec<_>>();
+	tags.sort_unstable();
+	tags
+}
+
+fn paths(openapi: &Value) -> Vec<&str> {
+	let mut paths = openapi["paths"]
+		.as_object()
+		.unwrap()
+		.keys()
+		.map(String::as_str)
+		.collect::<Vec<_>>();
+	paths.sort_unstable();
+	paths
+}
+
+#[test]
+fn separate_documents() {
+	let server = server();
+
+	let public = openapi(&server, "openapi");
+	assert_eq!(public["info"]["title"], "Public");
+	assert_eq!(paths(&public), vec!["/public", "/shared"]);
+	assert_eq!(tags(&public), vec!["Public", "Shared"]);
+
+	let admin = openapi(&server, "admin/openapi");
+	assert_eq!(admin["info"]["title"], "Admin");
+	assert_eq!(paths(&admin), vec!["/admin/ban", "/shared"]);
+	assert_eq!(tags(&admin), vec!["Admin", "Shared"]);
+}
+
+#[test]
+fn hidden_endpoints_are_routed() {
+	let server = server();
+	let res = server
+		.client()
+		.get("http://localhost/public/42")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+	let res = server
+		.client()
+		.post("http://localhost/admin/ban", "", gotham::mime::TEXT_PLAIN)
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NO_CONTENT);
+}
+
+#[test]
+#[should_panic(expected = "There is no OpenAPI document called \"internal\"")]
+fn unknown_document() {
+	build_simple_router(|router| {
+		router.with_openapi(info("Public"), |mut router| {
+			router.in_documents(&["internal"], |router| {
+				router.resource::<AdminResource>("admin");
+			});
+		});
+	});
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "openapi")]
+
+use gotham_restful::*;
+use serde_json::{json, Value};
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+#[read_all]
+fn read_all() {}
+
+#[test]
+fn openapi_info() {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: vec!["https://api.example.org/v1".to_owned()]
+	};
+	let spec = OpenapiSpec::new(info, |mut router| {
+		router.description("This is a **test**.");
+		router.terms_of_service("https://example.org/terms");
+		router.contact(OpenapiContact {
+			name: Some("API Support".to_owned()),
+			email: Some("support@example.org".to_owned()),
+			..Default::default()
+		});
+		router.license(OpenapiLicense {
+			name: "Apache-2.0".to_owned(),
+			url: Some("https://www.apache.org/licenses/LICENSE-2.0".to_owned())
+		});
+		router.external_docs(OpenapiExternalDocs {
+			url: "https://example.org/docs".to_owned(),
+			description: None
+		});
+		router.add_server(OpenapiServer {
+			url: "https://{region}.example.org/v1".to_owned(),
+			description: Some("Regional server".to_owned()),
+			variables: [("region".to_owned(), OpenapiServerVariable {
+				default: "eu".to_owned(),
+				enumeration: vec!["eu".to_owned(), "us".to_owned()],
+				description: None
+			})]
+			.into()
+		});
+		router.resource::<FooResource>("foo");
+	});
+	let openapi: Value = serde_json::from_str(&spec.to_json().unwrap()).unwrap();
+
+	assert_eq!(
+		openapi["info"],
+		json!({
+			"title": "Test",
+			"version": "1.2.3",
+			"description": "This is a **test**.",
+			"termsOfService": "https://example.org/terms",
+			"contact": {
+				"name": "API Support",
+				"email": "support@example.org"
+			},
+			"license": {
+				"name": "Apache-2.0",
+				"url": "https://www.apache.org/licenses/LICENSE-2.0"
+			}
+		})
+	);
+	assert_eq!(
+		openapi["externalDocs"],
+		json!({ "url": "https://example.org/docs" })
+	);
+	assert_eq!(
+		openapi["servers"],
+		json!([
+			{ "url": "https://api.example.org/v1" },
+			{
+				"url": "https://{region}.example.org/v1",
+				"description": "Regional server",
+				"variables": {
+					"region": {
+						"default": "eu",
+						"enum": ["eu", "us"]
+					}
+				}
+			}
+		])
+	);
+}
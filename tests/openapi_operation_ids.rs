@@ -0,0 +1,116 @@
+#![cfg(feature = "openapi")]
+
+use gotham::{hyper::Method, router::build_simple_router, test::TestServer};
+use gotham_restful::*;
+use serde_json::Value;
+
+#[derive(Resource)]
+#[resource(read_all, read, custom_search)]
+struct FooResource;
+
+#[read_all]
+fn read_all() {}
+
+#[read]
+fn read(_id: u64) {}
+
+#[endpoint(method = "Method::GET", uri = "search")]
+fn custom_search() {}
+
+#[derive(Resource)]
+#[resource(first, second)]
+struct DuplicateResource;
+
+#[endpoint(method = "Method::GET", uri = "first", operation_id = "duplicate")]
+fn first() {}
+
+#[endpoint(method = "Method::GET", uri = "second", operation_id = "duplicate")]
+fn second() {}
+
+fn openapi<R: ResourceWithSchema>(style: OperationIdStyle, paths: &[&str]) -> Value {
+	let server = TestServer::new(build_simple_router(|router| {
+		let info = OpenapiInfo {
+			title: "Test".to_owned(),
+			version: "1.2.3".to_owned(),
+			urls: Vec::new()
+		};
+		router.with_openapi(info, |mut router| {
+			router.operation_id_style(style);
+			for path in paths {
+				router.resource::<R>(path);
+			}
+			router.openapi_spec("openapi");
+		});
+	}))
+	.unwrap();
+	let body = server
+		.client()
+		.get("http://localhost/openapi")
+		.perform()
+		.unwrap()
+		.read_body()
+		.unwrap();
+	serde_json::from_slice(&body).unwrap()
+}
+
+fn operation_id<'a>(openapi: &'a Value, path: &str, method: &str) -> &'a str {
+	openapi["paths"][path][method]["operationId"]
+		.as_str()
+		.unwrap()
+}
+
+#[test]
+fn legacy_operation_ids() {
+	let openapi = openapi::<FooResource>(OperationIdStyle::default(), &["my-foo"]);
+	assert_eq!(operation_id(&openapi, "/my-foo", "get"), "read_all_my-foo");
+	assert_eq!(operation_id(&openapi, "/my-foo/{id}", "get"), "read_my-foo");
+	assert_eq!(
+		operation_id(&openapi, "/my-foo/search", "get"),
+		"custom_search_my-foo"
+	);
+}
+
+#[test]
+fn sanitized_operation_ids() {
+	let openapi = openapi::<FooResource>(OperationIdStyle::SnakeCase, &["my-foo"]);
+	assert_eq!(operation_id(&openapi, "/my-foo", "get"), "read_all_my_foo");
+	assert_eq!(operation_id(&openapi, "/my-foo/{id}", "get"), "read_my_foo");
+	assert_eq!(
+		operation_id(&openapi, "/my-foo/search", "get"),
+		"custom_search_my_foo"
+	);
+}
+
+#[test]
+fn camel_case_operation_ids() {
+	let openapi = openapi::<FooResource>(OperationIdStyle::CamelCase, &["my-foo"]);
+	assert_eq!(operation_id(&openapi, "/my-foo", "get"), "readAllMyFoo");
+	assert_eq!(operation_id(&openapi, "/my-foo/{id}", "get"), "readMyFoo");
+	assert_eq!(
+		operation_id(&openapi, "/my-foo/search", "get"),
+		"customSearchMyFoo"
+	);
+}
+
+#[test]
+fn unique_operation_ids() {
+	let openapi = openapi::<FooResource>(OperationIdStyle::SnakeCase, &["my-foo", "my_foo"]);
+	assert_eq!(operation_id(&openapi, "/my-foo", "get"), "read_all_my_foo");
+	assert_eq!(
+		operation_id(&openapi, "/my_foo", "get"),
+		"read_all_my_foo_2"
+	);
+}
+
+#[test]
+fn duplicate_manual_operation_ids() {
+	let openapi = openapi::<DuplicateResource>(OperationIdStyle::default(), &["duplicate"]);
+	assert_eq!(
+		operation_id(&openapi, "/duplicate/first", "get"),
+		"duplicate"
+	);
+	assert_eq!(
+		operation_id(&openapi, "/duplicate/second", "get"),
+		"duplicate_2"
+	);
+}
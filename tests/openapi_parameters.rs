@@ -0,0 +1,136 @@
+#![cfg(feature = "openapi")]
+#![allow(deprecated)]
+
+use gotham::prelude::*;
+use gotham_restful::*;
+use openapi_type::OpenapiType;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Resource)]
+#[resource(read_with, patch, update_all)]
+struct FooResource;
+
+#[derive(Clone, Deserialize, OpenapiType, StateData, StaticResponseExtender)]
+struct FooPath {
+	/// The id of the foo.
+	id: u64
+}
+
+#[openapi_fields]
+#[derive(Clone, Deserialize, OpenapiType, StateData, StaticResponseExtender)]
+#[serde(rename_all = "camelCase")]
+struct FooQuery {
+	/// Only return the foo if it was changed since this date.
+	#[openapi(example = "2024-01-01")]
+	since: Option<String>,
+	#[deprecated]
+	#[openapi(example = 10)]
+	limit: Option<u16>,
+	#[serde(default)]
+	with_bar: bool
+}
+
+#[openapi_fields]
+#[derive(Deserialize, OpenapiType)]
+struct FooBody {
+	#[openapi(rename = "fooName", example = "foo")]
+	name: String,
+	#[deprecated]
+	bar: Option<String>
+}
+
+#[endpoint(method = "gotham::hyper::Method::GET", uri = ":id", params = true)]
+fn read_with(path: FooPath, query: FooQuery) -> Success<String> {
+	format!(
+		"{} {:?} {:?} {}",
+		path.id, query.since, query.limit, query.with_bar
+	)
+	.into()
+}
+
+#[deprecated]
+#[endpoint(method = "gotham::hyper::Method::PATCH", uri = "", body = true)]
+fn patch(_body: String) {}
+
+#[update_all]
+fn update_all(body: FooBody) -> Success<String> {
+	format!("{} {:?}", body.name, body.bar).into()
+}
+
+fn openapi() -> Value {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let spec = OpenapiSpec::new(info, |mut router| {
+		router.resource::<FooResource>("foo");
+	});
+	serde_json::from_str(&spec.to_json().unwrap()).unwrap()
+}
+
+fn parameter<'a>(openapi: &'a Value, name: &str) -> &'a Value {
+	openapi["paths"]["/foo/{id}"]["get"]["parameters"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.find(|param| param["name"] == name)
+		.unwrap()
+}
+
+#[test]
+fn parameter_descriptions() {
+	let openapi = openapi();
+	let id = parameter(&openapi, "id");
+	assert_eq!(id["in"], "path");
+	assert_eq!(id["description"], "The id of the foo.");
+	assert!(id["schema"].get("description").is_none());
+
+	let since = parameter(&openapi, "since");
+	assert_eq!(since["in"], "query");
+	assert_eq!(
+		since["description"],
+		"Only return the foo if it was changed since this date."
+	);
+
+	let limit = parameter(&openapi, "limit");
+	assert!(limit.get("description").is_none());
+}
+
+#[test]
+fn parameter_examples_and_deprecation() {
+	let openapi = openapi();
+	let since = parameter(&openapi, "since");
+	assert_eq!(since["example"], "2024-01-01");
+	assert!(since.get("deprecated").is_none());
+
+	let limit = parameter(&openapi, "limit");
+	assert_eq!(limit["example"], 10);
+	assert_eq!(limit["deprecated"], true);
+	assert!(limit["schema"].get("example").is_none());
+	assert!(limit["schema"].get("deprecated").is_none());
+
+	let with_bar = parameter(&openapi, "withBar");
+	assert!(with_bar.get("example").is_none());
+	assert!(with_bar.get("deprecated").is_none());
+}
+
+#[test]
+fn body_examples_and_deprecation() {
+	let openapi = openapi();
+	let body = &openapi["components"]["schemas"]["FooBody"]["properties"];
+	assert_eq!(body["fooName"]["example"], "foo");
+	assert!(body["fooName"].get("deprecated").is_none());
+	assert!(body["bar"].get("example").is_none());
+	assert_eq!(body["bar"]["deprecated"], true);
+}
+
+#[test]
+fn deprecated_endpoint() {
+	let openapi = openapi();
+	assert_eq!(openapi["paths"]["/foo"]["patch"]["deprecated"], true);
+	assert!(openapi["paths"]["/foo/{id}"]["get"]
+		.get("deprecated")
+		.is_none());
+}
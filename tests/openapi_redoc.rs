@@ -0,0 +1,44 @@
+#![cfg(feature = "openapi")]
+
+use gotham::{
+	hyper::{header::CONTENT_SECURITY_POLICY, StatusCode},
+	router::builder::*,
+	test::TestServer
+};
+use gotham_restful::*;
+
+#[test]
+fn openapi_redoc_csp() {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let server = TestServer::new(build_simple_router(|router| {
+		router.with_openapi(info, |mut router| {
+			router.openapi_doc("/");
+		});
+	}))
+	.unwrap();
+
+	let res = server.client().get("http://localhost/").perform().unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	let csp = res
+		.headers()
+		.get(CONTENT_SECURITY_POLICY)
+		.unwrap()
+		.to_str()
+		.unwrap()
+		.to_owned();
+	assert!(csp.contains("connect-src 'self';"));
+	assert!(!csp.contains("font-src"));
+	assert!(!csp.contains("googleapis"));
+
+	let html = res.read_utf8_body().unwrap();
+	assert!(!html.contains("googleapis"));
+
+	// the pinned Redoc bundle is the only resource loaded from another origin
+	for (i, _) in html.match_indices("https://") {
+		assert!(html[i..].starts_with("https://cdn.redoc.ly/redoc/v2.5.3/bundles/"));
+	}
+}
@@ -0,0 +1,162 @@
+#![cfg(feature = "openapi")]
+
+use gotham::hyper::{
+	header::{CACHE_CONTROL, ETAG, LOCATION},
+	Method, StatusCode
+};
+use gotham_restful::*;
+use openapi_type::{OpenapiSchema, OpenapiType};
+use serde_json::{json, Value};
+
+#[derive(Resource)]
+#[resource(redirect, read, read_all, cached)]
+struct FooResource;
+
+#[derive(Debug, ResourceError)]
+enum Error {
+	#[status(NOT_FOUND)]
+	#[display("The foo does not exist.")]
+	NotFound,
+
+	#[status(BAD_REQUEST)]
+	#[display("Invalid foo: {0}")]
+	Invalid(String)
+}
+
+#[endpoint(method = "Method::GET", uri = "redirect")]
+fn redirect() -> Redirect {
+	Redirect {
+		to: "/foo".to_owned()
+	}
+}
+
+#[read]
+fn read(id: u64) -> Result<NoContent, Error> {
+	match id {
+		0 => Err(Error::Invalid(id.to_string())),
+		_ => Err(Error::NotFound)
+	}
+}
+
+struct Tagged;
+
+impl IntoResponse for Tagged {
+	type Err = std::convert::Infallible;
+
+	fn into_response(
+		self
+	) -> gotham_restful::private::BoxFuture<'static, Result<Response, Self::Err>> {
+		Box::pin(async { Ok(Response::no_content()) })
+	}
+}
+
+impl ResponseSchema for Tagged {
+	fn schema(code: StatusCode) -> OpenapiSchema {
+		assert_eq!(code, StatusCode::OK);
+		<()>::schema()
+	}
+
+	fn headers(code: StatusCode) -> Vec<ResponseHeader> {
+		assert_eq!(code, StatusCode::OK);
+		vec![ResponseHeader::new(ETAG).with_description("The version of the foos.")]
+	}
+
+	fn description(code: StatusCode) -> Option<String> {
+		assert_eq!(code, StatusCode::OK);
+		Some("All foos.".to_owned())
+	}
+}
+
+#[read_all]
+fn read_all() -> Tagged {
+	Tagged
+}
+
+fn cache_control_header(code: StatusCode) -> Vec<ResponseHeader> {
+	assert_eq!(code, StatusCode::NO_CONTENT);
+	vec![ResponseHeader::new(CACHE_CONTROL).required()]
+}
+
+#[endpoint(
+	method = "Method::GET",
+	uri = "cached",
+	headers = "cache_control_header"
+)]
+fn cached() -> NoContent {
+	let mut res = NoContent::default();
+	res.header(CACHE_CONTROL, "max-age=3600".parse().unwrap());
+	res
+}
+
+fn openapi() -> Value {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let spec = OpenapiSpec::new(info, |mut router| {
+		router.resource::<FooResource>("foo");
+	});
+	serde_json::from_str(&spec.to_json().unwrap()).unwrap()
+}
+
+#[test]
+fn redirect_location_header() {
+	let openapi = openapi();
+	let response = &openapi["paths"]["/foo/redirect"]["get"]["responses"]["303"];
+	assert_eq!(response["description"], "See Other");
+	assert_eq!(
+		response["headers"],
+		json!({
+			LOCATION.as_str(): {
+				"description": "The location to redirect to.",
+				"required": true,
+				"style": "simple",
+				"schema": { "type": "string" }
+			}
+		})
+	);
+}
+
+#[test]
+fn resource_error_descriptions() {
+	let openapi = openapi();
+	let responses = &openapi["paths"]["/foo/{id}"]["get"]["responses"];
+	assert_eq!(responses["404"]["description"], "The foo does not exist.");
+	// display strings with format arguments cannot be used as a description
+	assert_eq!(responses["400"]["description"], "Bad Request");
+	assert_eq!(responses["204"]["description"], "No Content");
+}
+
+#[test]
+fn custom_headers_and_description() {
+	let openapi = openapi();
+	let response = &openapi["paths"]["/foo"]["get"]["responses"]["200"];
+	assert_eq!(response["description"], "All foos.");
+	assert_eq!(
+		response["headers"],
+		json!({
+			"etag": {
+				"description": "The version of the foos.",
+				"style": "simple",
+				"schema": { "type": "string" }
+			}
+		})
+	);
+}
+
+#[test]
+fn endpoint_headers() {
+	let openapi = openapi();
+	let response = &openapi["paths"]["/foo/cached"]["get"]["responses"]["204"];
+	assert_eq!(
+		response["headers"],
+		json!({
+			"cache-control": {
+				"required": true,
+				"style": "simple",
+				"schema": { "type": "string" }
+			}
+		})
+	);
+}
@@ -25,7 +25,7 @@ use util::test_openapi_response;
 const IMAGE_RESPONSE : &[u8] = b"iVBORw0KGgoAAAANSUhEUgAAAAEAAAABAQMAAAAl21bKAAAAA1BMVEUA/wA0XsCoAAAAAXRSTlN/gFy0ywAAAApJREFUeJxjYgAAAAYAAzY3fKgAAAAASUVORK5CYII=";
 
 #[derive(Resource)]
-#[resource(get_image, set_image)]
+#[resource(get_image, set_image, tag = "Images")]
 struct ImageResource;
 
 #[derive(FromBody, RequestBody)]
@@ -40,6 +40,7 @@ fn get_image(_id: u64) -> Raw<&'static [u8]> {
 #[update(operation_id = "setImage")]
 fn set_image(_id: u64, _image: Image) {}
 
+/// Secrets that are only available to authenticated users.
 #[derive(Resource)]
 #[resource(read_secret, search_secret)]
 struct SecretResource;
@@ -109,7 +110,11 @@ fn teapot_schema(code: StatusCode) -> OpenapiSchema {
 	Binary::schema()
 }
 
-#[read_all(status_codes = "teapot_status_codes", schema = "teapot_schema")]
+#[read_all(
+	status_codes = "teapot_status_codes",
+	schema = "teapot_schema",
+	tag = "Teapot"
+)]
 fn coffee_read_all() -> Response {
 	Response::new(
 		StatusCode::IM_A_TEAPOT,
@@ -165,3 +170,27 @@ fn openapi_specification() {
 		"tests/openapi_specification.json"
 	);
 }
+
+#[test]
+fn openapi_specification_export() {
+	let info = OpenapiInfo {
+		title: "This is just a test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: vec!["http://localhost:12345/api/v1".to_owned()]
+	};
+	let spec = OpenapiSpec::new(info, |mut router| {
+		router.resource::<ImageResource>("/img");
+		router.resource::<SecretResource>("secret");
+		router.resource::<CoffeeResource>("coffee");
+		router.resource::<CustomResource>("custom");
+	})
+	.with_auth_source(AuthSource::AuthorizationHeader);
+
+	let body: serde_json::Value = serde_json::from_str(&spec.to_json().unwrap()).unwrap();
+	let expected: serde_json::Value =
+		serde_json::from_reader(std::fs::File::open("tests/openapi_specification.json").unwrap())
+			.unwrap();
+	assert_eq!(body, expected);
+
+	openapi::assert_no_breaking_changes("tests/openapi_specification.json", &spec);
+}
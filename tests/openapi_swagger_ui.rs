@@ -0,0 +1,83 @@
+#![cfg(feature = "swagger-ui")]
+
+use gotham::{
+	hyper::{
+		header::{CONTENT_SECURITY_POLICY, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+		StatusCode
+	},
+	mime::TEXT_PLAIN,
+	router::builder::*,
+	test::TestServer
+};
+use gotham_restful::*;
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+#[read_all]
+fn read_all() -> Raw<&'static [u8]> {
+	Raw::new(b"foo", TEXT_PLAIN)
+}
+
+#[test]
+fn openapi_swagger_ui() {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: vec!["https://api.example.org/v1".to_owned()]
+	};
+	let server = TestServer::new(build_simple_router(|router| {
+		router.with_openapi(info, |mut router| {
+			router.add_server(OpenapiServer {
+				url: "https://{region}.example.org/v1".to_owned(),
+				variables: [("region".to_owned(), OpenapiServerVariable {
+					default: "eu".to_owned(),
+					enumeration: vec!["eu".to_owned(), "us".to_owned()],
+					..Default::default()
+				})]
+				.into(),
+				..Default::default()
+			});
+			router.openapi_swagger_ui("swagger");
+			router.resource::<FooResource>("foo");
+		});
+	}))
+	.unwrap();
+
+	let res = server
+		.client()
+		.get("http://localhost/swagger")
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::OK);
+	assert_eq!(
+		res.headers().get(CONTENT_TYPE).unwrap(),
+		"text/html; charset=utf-8"
+	);
+	let csp = res
+		.headers()
+		.get(CONTENT_SECURITY_POLICY)
+		.unwrap()
+		.to_str()
+		.unwrap()
+		.to_owned();
+	assert!(csp.contains("script-src 'unsafe-inline' 'sha256-"));
+	assert!(csp.contains(
+		"connect-src 'self' https://api.example.org/v1 https://eu.example.org/v1 \
+		 https://us.example.org/v1;"
+	));
+	let etag = res.headers().get(ETAG).unwrap().clone();
+	let html = res.read_utf8_body().unwrap();
+	assert!(html.contains(r#"<div id="swagger-ui"></div>"#));
+	assert!(!html.contains("<script src="));
+	assert!(!html.contains("<link"));
+
+	let res = server
+		.client()
+		.get("http://localhost/swagger")
+		.with_header(IF_NONE_MATCH, etag)
+		.perform()
+		.unwrap();
+	assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+}
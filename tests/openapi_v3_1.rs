@@ -0,0 +1,61 @@
+#![cfg(feature = "openapi")]
+
+use gotham::{router::builder::*, test::TestServer};
+use gotham_restful::*;
+use serde_json::Value;
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+#[read_all]
+fn read_all() -> Success<Vec<Option<String>>> {
+	vec![Some("foo".to_owned()), None].into()
+}
+
+fn openapi(openapi_version: OpenapiVersion) -> Value {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let server = TestServer::new(build_simple_router(|router| {
+		router.with_openapi(info, |mut router| {
+			router.openapi_version(openapi_version);
+			router.openapi_spec("openapi");
+			router.resource::<FooResource>("foo");
+		});
+	}))
+	.unwrap();
+
+	let res = server
+		.client()
+		.get("http://localhost/openapi")
+		.perform()
+		.unwrap()
+		.read_body()
+		.unwrap();
+	serde_json::from_slice(&res).unwrap()
+}
+
+fn response_schema(openapi: &Value) -> &Value {
+	&openapi["paths"]["/foo"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+}
+
+#[test]
+fn openapi_v3_0_is_default() {
+	let openapi = openapi(OpenapiVersion::default());
+	assert_eq!(openapi["openapi"], "3.0.2");
+	let items = &response_schema(&openapi)["items"];
+	assert_eq!(items["type"], "string");
+	assert_eq!(items["nullable"], true);
+}
+
+#[test]
+fn openapi_v3_1() {
+	let openapi = openapi(OpenapiVersion::V3_1);
+	assert_eq!(openapi["openapi"], "3.1.0");
+	let items = &response_schema(&openapi)["items"];
+	assert_eq!(items["type"], serde_json::json!(["string", "null"]));
+	assert!(items.get("nullable").is_none());
+}
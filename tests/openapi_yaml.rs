@@ -0,0 +1,49 @@
+#![cfg(feature = "openapi-yaml")]
+
+use gotham::{hyper::header::CONTENT_TYPE, mime::TEXT_PLAIN, router::builder::*, test::TestServer};
+use gotham_restful::*;
+use serde_json::Value;
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+#[read_all]
+fn read_all() -> Raw<&'static [u8]> {
+	Raw::new(b"foo", TEXT_PLAIN)
+}
+
+#[test]
+fn openapi_yaml() {
+	let info = OpenapiInfo {
+		title: "Test".to_owned(),
+		version: "1.2.3".to_owned(),
+		urls: Vec::new()
+	};
+	let server = TestServer::new(build_simple_router(|router| {
+		router.with_openapi(info, |mut router| {
+			router.openapi_spec("openapi.json");
+			router.openapi_spec_yaml("openapi.yaml");
+			router.resource::<FooResource>("foo");
+		});
+	}))
+	.unwrap();
+
+	let res = server
+		.client()
+		.get("http://localhost/openapi.json")
+		.perform()
+		.unwrap();
+	let json: Value = serde_json::from_slice(&res.read_body().unwrap()).unwrap();
+
+	let res = server
+		.client()
+		.get("http://localhost/openapi.yaml")
+		.perform()
+		.unwrap();
+	assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/yaml");
+	let yaml = res.read_utf8_body().unwrap();
+	assert!(yaml.starts_with("openapi: 3.0.2\n"));
+	let yaml: Value = serde_yaml::from_str(&yaml).unwrap();
+	assert_eq!(yaml, json);
+}
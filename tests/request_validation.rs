@@ -0,0 +1,122 @@
+#![cfg(feature = "openapi")]
+
+use gotham::{
+	hyper::StatusCode,
+	mime::{APPLICATION_JSON, TEXT_PLAIN},
+	router::builder::*,
+	test::TestServer
+};
+use gotham_restful::*;
+use openapi_type::OpenapiType;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Resource)]
+#[resource(create)]
+struct FooResource;
+
+#[derive(Deserialize, OpenapiType)]
+enum Color {
+	Red,
+	Green
+}
+
+#[derive(Deserialize, OpenapiType)]
+#[allow(dead_code)]
+struct Foo {
+	name: String,
+	color: Color
+}
+
+#[create]
+fn create(_body: Foo) -> Raw<&'static [u8]> {
+	Raw::new(b"created", TEXT_PLAIN)
+}
+
+fn server(validate_requests: bool) -> TestServer {
+	TestServer::new(build_simple_router(|router| {
+		let info = OpenapiInfo {
+			title: "Test".to_owned(),
+			version: "1.2.3".to_owned(),
+			urls: Vec::new()
+		};
+		router.with_openapi(info, |mut router| {
+			if validate_requests {
+				router.validate_requests();
+			}
+			router.resource::<FooResource>("foo");
+			router.openapi_spec("openapi");
+		});
+	}))
+	.unwrap()
+}
+
+fn create_foo(server: &TestServer, body: Value) -> (StatusCode, Vec<u8>) {
+	let res = server
+		.client()
+		.post("http://localhost/foo", body.to_string(), APPLICATION_JSON)
+		.perform()
+		.unwrap();
+	(res.status(), res.read_body().unwrap())
+}
+
+#[test]
+fn valid_request() {
+	let server = server(true);
+	let (status, body) = create_foo(&server, json!({ "name": "foo", "color": "Red" }));
+	assert_eq!(status, StatusCode::OK);
+	assert_eq!(body, b"created");
+}
+
+#[test]
+fn invalid_request() {
+	let server = server(true);
+	let (status, body) = create_foo(&server, json!({ "color": "Blue" }));
+	assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+	let body: Value = serde_json::from_slice(&body).unwrap();
+	assert_eq!(body["error"], true);
+	assert_eq!(
+		body["errors"],
+		json!([
+			{
+				"location": "body",
+				"pointer": "/name",
+				"message": "is required"
+			},
+			{
+				"location": "body",
+				"pointer": "/color",
+				"message": r#"must be one of "Red", "Green""#
+			}
+		])
+	);
+}
+
+#[test]
+fn validation_is_opt_in() {
+	let server = server(false);
+	let (status, _) = create_foo(&server, json!({ "color": "Blue" }));
+	assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn validation_is_documented() {
+	for validate_requests in [true, false] {
+		let res = server(validate_requests)
+			.client()
+			.get("http://localhost/openapi")
+			.perform()
+			.unwrap()
+			.read_body()
+			.unwrap();
+		let openapi: Value = serde_json::from_slice(&res).unwrap();
+		let response = &openapi["paths"]["/foo"]["post"]["responses"]["422"];
+		match validate_requests {
+			true => assert_eq!(
+				response["content"]["application/json"]["schema"]["$ref"],
+				"#/components/schemas/ValidationErrors"
+			),
+			false => assert!(response.is_null())
+		}
+	}
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "client")]
+
+use futures_util::future;
+use gotham::{
+	hyper::{header::AUTHORIZATION, HeaderMap, Method, StatusCode},
+	mime::TEXT_PLAIN,
+	prelude::*,
+	router::build_simple_router,
+	state::State
+};
+use gotham_restful::{client::*, *};
+#[cfg(feature = "openapi")]
+use openapi_type::OpenapiType;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tokio::{net::TcpListener, runtime::Runtime};
+
+#[derive(Resource, ResourceClient)]
+#[resource(read_all, read, search, create, delete, custom)]
+struct FooResource;
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(OpenapiType))]
+struct Foo {
+	id: u64,
+	name: String
+}
+
+#[derive(Clone, Deserialize, Serialize, StateData, StaticResponseExtender)]
+#[cfg_attr(feature = "openapi", derive(OpenapiType))]
+struct FooSearch {
+	name: String,
+	ids: Vec<u64>
+}
+
+#[derive(Clone, Deserialize, Serialize, StateData, StaticResponseExtender)]
+#[cfg_attr(feature = "openapi", derive(OpenapiType))]
+struct CustomPlaceholders {
+	from: u64,
+	to: String
+}
+
+#[derive(Debug, ResourceError)]
+enum Error {
+	#[status(NOT_FOUND)]
+	#[display("Not found")]
+	NotFound
+}
+
+/// Return all foos.
+#[read_all]
+fn read_all() -> Success<Vec<Foo>> {
+	vec![Foo {
+		id: 1,
+		name: "one".to_owned()
+	}]
+	.into()
+}
+
+#[read]
+fn read(id: u64) -> Result<Foo, Error> {
+	match id {
+		0 => Err(Error::NotFound),
+		id => Ok(Foo {
+			id,
+			name: id.to_string()
+		})
+	}
+}
+
+#[search]
+fn search(query: FooSearch) -> Success<Vec<Foo>> {
+	query
+		.ids
+		.into_iter()
+		.map(|id| Foo {
+			id,
+			name: query.name.clone()
+		})
+		.collect::<Vec<_>>()
+		.into()
+}
+
+#[create]
+fn create(body: Foo) -> Success<Foo> {
+	body.into()
+}
+
+#[delete]
+fn delete(_id: u64) {}
+
+#[endpoint(method = "Method::GET", uri = "custom/:from/:to")]
+fn custom(state: &mut State, placeholders: CustomPlaceholders) -> Raw<String> {
+	let auth = HeaderMap::borrow_from(state)
+		.get(AUTHORIZATION)
+		.and_then(|value| value.to_str().ok())
+		.unwrap_or_default();
+	let body = format!("{} {} {auth}", placeholders.from, placeholders.to);
+	Raw::new(body, TEXT_PLAIN)
+}
+
+mod bar {
+	use gotham_restful::*;
+
+	/// The endpoints are private to this module, but the client is not.
+	#[derive(Resource, ResourceClient)]
+	#[resource(read_bars)]
+	pub struct BarResource;
+
+	#[read_all]
+	fn read_bars() -> Success<Vec<u64>> {
+		vec![1, 2, 3].into()
+	}
+}
+
+fn with_client<F, Fut>(test: F)
+where
+	F: FnOnce(Client) -> Fut,
+	Fut: Future<Output = ()>
+{
+	let runtime = Runtime::new().unwrap();
+	let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+	let addr = listener.local_addr().unwrap();
+	let router = build_simple_router(|route| {
+		route.resource::<FooResource>("foo");
+		route.resource::<bar::BarResource>("bar");
+	});
+	runtime.spawn(gotham::bind_server(listener, router, future::ok));
+
+	let client =
+		Client::new(format!("http://{addr}")).with_header(AUTHORIZATION, "secret".parse().unwrap());
+	runtime.block_on(test(client));
+}
+
+#[test]
+fn resource_client() {
+	with_client(|client| async move {
+		let client = FooResourceClient::new(client, "foo");
+		assert_eq!(client.read_all().await.unwrap(), vec![Foo {
+			id: 1,
+			name: "one".to_owned()
+		}]);
+
+		assert_eq!(client.read(42).await.unwrap(), Foo {
+			id: 42,
+			name: "42".to_owned()
+		});
+		match client.read(0).await {
+			Err(ClientError::Status { status, .. }) => assert_eq!(status, StatusCode::NOT_FOUND),
+			res => panic!("Expected a 404 response, got {res:?}")
+		}
+
+		let query = FooSearch {
+			name: "a b&c".to_owned(),
+			ids: vec![1, 2]
+		};
+		let found = client.search(query).await.unwrap();
+		assert_eq!(found.len(), 2);
+		assert_eq!(found[1], Foo {
+			id: 2,
+			name: "a b&c".to_owned()
+		});
+
+		let foo = Foo {
+			id: 3,
+			name: "three".to_owned()
+		};
+		let created = client.create(foo).await.unwrap();
+		assert_eq!(created.id, 3);
+
+		client.delete(3).await.unwrap();
+
+		let placeholders = CustomPlaceholders {
+			from: 1,
+			to: "a/b".to_owned()
+		};
+		let raw = client.custom(placeholders).await.unwrap();
+		assert_eq!(raw.mime, TEXT_PLAIN);
+		assert_eq!(raw.raw, b"1 a/b secret");
+	});
+}
+
+#[test]
+fn resource_client_outside_of_module() {
+	with_client(|client| async move {
+		let client = bar::BarResourceClient::new(client, "bar");
+		assert_eq!(client.read_bars().await.unwrap(), vec![1, 2, 3]);
+	});
+}
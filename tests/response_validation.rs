@@ -0,0 +1,96 @@
+#![cfg(feature = "validate-responses")]
+
+use futures_util::future::{self, BoxFuture, FutureExt};
+use gotham::{hyper::StatusCode, mime::APPLICATION_JSON, router::builder::*, test::TestServer};
+use gotham_restful::*;
+use openapi_type::{OpenapiSchema, OpenapiType};
+use serde_json::{json, Value};
+use std::convert::Infallible;
+
+#[derive(Resource)]
+#[resource(read)]
+struct FooResource;
+
+#[derive(OpenapiType)]
+#[allow(dead_code)]
+struct Foo {
+	id: u64
+}
+
+/// A response that claims to return a [Foo] with status 200, but can return anything.
+struct Drifted(StatusCode, Value);
+
+impl IntoResponse for Drifted {
+	type Err = Infallible;
+
+	fn into_response(self) -> BoxFuture<'static, Result<Response, Infallible>> {
+		let res = Response::new(self.0, self.1.to_string(), Some(APPLICATION_JSON));
+		future::ok(res).boxed()
+	}
+}
+
+impl ResponseSchema for Drifted {
+	fn schema(code: StatusCode) -> OpenapiSchema {
+		assert_eq!(code, StatusCode::OK);
+		Foo::schema()
+	}
+}
+
+#[read]
+fn read(id: u64) -> Drifted {
+	match id {
+		1 => Drifted(StatusCode::OK, json!({ "id": 1 })),
+		2 => Drifted(StatusCode::OK, json!({ "id": "two" })),
+		_ => Drifted(StatusCode::CREATED, json!({ "id": id }))
+	}
+}
+
+fn server(mode: ResponseValidationMode) -> TestServer {
+	TestServer::new(build_simple_router(|router| {
+		let info = OpenapiInfo {
+			title: "Test".to_owned(),
+			version: "1.2.3".to_owned(),
+			urls: Vec::new()
+		};
+		router.with_openapi(info, |mut router| {
+			router.validate_responses(mode);
+			router.resource::<FooResource>("foo");
+		});
+	}))
+	.unwrap()
+}
+
+fn read_foo(server: &TestServer, id: u64) -> (StatusCode, Vec<u8>) {
+	let res = server
+		.client()
+		.get(format!("http://localhost/foo/{id}"))
+		.perform()
+		.unwrap();
+	(res.status(), res.read_body().unwrap())
+}
+
+#[test]
+fn valid_response() {
+	let server = server(ResponseValidationMode::Panic);
+	let (status, body) = read_foo(&server, 1);
+	assert_eq!(status, StatusCode::OK);
+	assert_eq!(body, br#"{"id":1}"#);
+}
+
+#[test]
+fn panic_on_invalid_response() {
+	let server = server(ResponseValidationMode::Panic);
+	assert_eq!(read_foo(&server, 2).0, StatusCode::INTERNAL_SERVER_ERROR);
+	assert_eq!(read_foo(&server, 3).0, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[test]
+fn log_invalid_response() {
+	let server = server(ResponseValidationMode::Log);
+	let (status, body) = read_foo(&server, 2);
+	assert_eq!(status, StatusCode::OK);
+	assert_eq!(body, br#"{"id":"two"}"#);
+	let (status, body) = read_foo(&server, 3);
+	assert_eq!(status, StatusCode::CREATED);
+	assert_eq!(body, br#"{"id":3}"#);
+}
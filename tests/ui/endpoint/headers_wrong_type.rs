@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate gotham_restful;
+
+#[derive(Resource)]
+#[resource(read_all)]
+struct FooResource;
+
+fn headers(_: u16) -> Vec<String> {
+	unimplemented!()
+}
+
+#[read_all(headers = "headers")]
+async fn read_all() {}
+
+fn main() {}